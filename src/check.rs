@@ -0,0 +1,457 @@
+//! Checking a merged site for broken links.
+//!
+//! Links between crates, and to the shared files such as `static.files/`, the source pages and
+//! the implementor lists, are all relative, so a file that went missing in a merge only shows up
+//! as a page that fails to load. [`check`] follows every relative link in the site instead.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Result;
+use regex::Regex;
+
+use crate::implementors::{self, ImplFile};
+use crate::merger::PER_CRATE_DIRS;
+use crate::{fsutil, html, latest, versions, MergeError};
+
+/// What a broken link is missing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkProblem {
+    /// The file the link leads to does not exist.
+    MissingFile,
+
+    /// The file exists, but has no element with the id the link's fragment names.
+    MissingAnchor,
+}
+
+/// A relative link that leads nowhere.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenLink {
+    /// The page (or implementor file) the link is in, relative to the site.
+    pub page: PathBuf,
+
+    /// The link, as written in the page.
+    pub href: String,
+
+    /// What the link is missing.
+    pub problem: LinkProblem,
+}
+
+impl fmt::Display for BrokenLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let problem = match self.problem {
+            LinkProblem::MissingFile => "no such file",
+            LinkProblem::MissingAnchor => "no such anchor",
+        };
+        write!(f, "{}: {} ({problem})", self.page.display(), self.href)
+    }
+}
+
+/// What [`check`] found.
+#[derive(Debug, Clone, Default)]
+pub struct CheckReport {
+    /// The number of pages and implementor files checked.
+    pub pages: usize,
+
+    /// The number of relative links followed.
+    pub links: usize,
+
+    /// The broken links, grouped by the crate whose pages they are in. Links in pages that do not
+    /// belong to a crate, such as the landing page, are listed under an empty name.
+    pub broken: BTreeMap<String, Vec<BrokenLink>>,
+}
+
+impl CheckReport {
+    /// The number of broken links.
+    pub fn broken_count(&self) -> usize {
+        self.broken.values().map(Vec::len).sum()
+    }
+}
+
+/// Follow every relative link in the HTML pages and implementor files of the merged site at
+/// `dest`, and report those that lead to a missing file or anchor.
+///
+/// Links to other sites, and the hidden directories doc-merge keeps next to a site it replaced,
+/// are skipped.
+pub fn check(dest: &Path) -> Result<CheckReport> {
+    let mut report = CheckReport::default();
+    let versions = versions::read(dest)?;
+    let href = Regex::new(r#"\s(?:href|src)="([^"]*)""#).expect("valid regex");
+    // links in implementor files are inside JSON strings, so their quotes are escaped
+    let impl_href = Regex::new(r#"\bhref=\\?"([^"\\]*)\\?""#).expect("valid regex");
+    let mut anchors = Anchors::default();
+
+    for rel in fsutil::walk_files(dest)? {
+        if rel.components().any(|component| {
+            component
+                .as_os_str()
+                .to_str()
+                .is_some_and(|name| name.starts_with('.'))
+        }) {
+            continue;
+        }
+        let path = dest.join(&rel);
+        let crate_name = crate_of(&rel, &versions);
+        let mut links = Vec::new();
+        if rel.extension().is_some_and(|ext| ext == "html") {
+            let page = fs::read_to_string(&path).map_err(MergeError::io(&path))?;
+            let dir = path.parent().unwrap_or(dest).to_owned();
+            links.extend(
+                href.captures_iter(&page)
+                    .map(|captures| (dir.clone(), captures[1].to_owned())),
+            );
+        } else if let Some(root) = implementors_root(dest, &rel) {
+            if rel.extension().is_none_or(|ext| ext != "js") {
+                continue;
+            }
+            // the links are relative to the root of the site the file belongs to
+            let file = ImplFile::read(&path)?;
+            for data in file.crates.values() {
+                links.extend(
+                    impl_href
+                        .captures_iter(&data.dump())
+                        .map(|captures| (root.clone(), captures[1].to_owned())),
+                );
+            }
+        } else {
+            continue;
+        }
+        report.pages += 1;
+
+        for (dir, href) in links {
+            let href = html::unescape(&href);
+            let Some((target, fragment)) = resolve(&dir, &path, &href) else {
+                continue;
+            };
+            report.links += 1;
+            let problem = if !target.is_file() {
+                Some(LinkProblem::MissingFile)
+            } else if target.extension().is_some_and(|ext| ext == "html")
+                && fragment.is_some_and(|fragment| !anchors.has(&target, fragment))
+            {
+                Some(LinkProblem::MissingAnchor)
+            } else {
+                None
+            };
+            if let Some(problem) = problem {
+                let link = BrokenLink {
+                    page: rel.clone(),
+                    href,
+                    problem,
+                };
+                // a page often repeats a link, such as in its sidebar and its body
+                let broken = report.broken.entry(crate_name.clone()).or_default();
+                if !broken.contains(&link) {
+                    broken.push(link);
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// The file a relative link in the page at `page` leads to, resolved against `dir`, along with
+/// its fragment. Returns `None` for links to other sites, and for links that are only a query.
+fn resolve<'a>(dir: &Path, page: &Path, href: &'a str) -> Option<(PathBuf, Option<&'a str>)> {
+    if href.is_empty() || href.starts_with("//") || has_scheme(href) {
+        return None;
+    }
+    let (href, fragment) = match href.split_once('#') {
+        Some((href, fragment)) => (href, Some(fragment).filter(|fragment| !fragment.is_empty())),
+        None => (href, None),
+    };
+    let href = href.split('?').next().unwrap_or_default();
+    if href.is_empty() {
+        return fragment.map(|fragment| (page.to_owned(), Some(fragment)));
+    }
+    let mut target = normalize(&dir.join(percent_decode(href)));
+    if href.ends_with('/') || target.is_dir() {
+        target.push("index.html");
+    }
+    Some((target, fragment))
+}
+
+/// Whether `href` starts with a URL scheme, such as `https:` or `mailto:`.
+fn has_scheme(href: &str) -> bool {
+    href.split_once(':').is_some_and(|(scheme, _)| {
+        !scheme.is_empty()
+            && !scheme.contains('/')
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+    })
+}
+
+/// The ids of the elements in each page, read as they are needed.
+#[derive(Debug, Default)]
+struct Anchors(HashMap<PathBuf, BTreeSet<String>>);
+
+impl Anchors {
+    /// Whether the page at `path` has an element that the fragment `fragment` leads to.
+    fn has(&mut self, path: &Path, fragment: &str) -> bool {
+        // source pages highlight lines and line ranges, such as `#10-20`, with JavaScript
+        if fragment
+            .split('-')
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return true;
+        }
+        let ids = self.0.entry(path.to_owned()).or_insert_with(|| {
+            let page = fs::read_to_string(path).unwrap_or_default();
+            Regex::new(r#"\s(?:id|name)="([^"]*)""#)
+                .expect("valid regex")
+                .captures_iter(&page)
+                .map(|captures| html::unescape(&captures[1]))
+                .collect()
+        });
+        ids.contains(fragment) || ids.contains(&percent_decode(fragment))
+    }
+}
+
+/// The crate the file at `rel` in the site belongs to, or an empty string for the files shared by
+/// the whole site.
+fn crate_of(rel: &Path, versions: &[String]) -> String {
+    let mut names = rel
+        .components()
+        .filter_map(|component| component.as_os_str().to_str())
+        .collect::<Vec<_>>();
+    if names.len() > 1
+        && (names[0] == latest::DIR || versions.iter().any(|version| version == names[0]))
+    {
+        names.remove(0);
+    }
+    match names.as_slice() {
+        [dir, crate_name, _, ..] if PER_CRATE_DIRS.contains(dir) => (*crate_name).to_owned(),
+        [dir, _, ..] if implementors::DIRS.contains(dir) => String::new(),
+        [dir, _, ..] if *dir != "static.files" => (*dir).to_owned(),
+        _ => String::new(),
+    }
+}
+
+/// If `rel` is an implementor file, the root of the site (or version of the site) it belongs to.
+fn implementors_root(dest: &Path, rel: &Path) -> Option<PathBuf> {
+    let mut root = dest.to_owned();
+    for component in rel.components() {
+        let name = component.as_os_str().to_str()?;
+        if implementors::DIRS.contains(&name) {
+            return Some(root);
+        }
+        root.push(name);
+    }
+    None
+}
+
+/// Resolve the `.` and `..` components of `path` without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Decode the `%XX` escapes in a link.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escape = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escape {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write a site of `(path, contents)` pages into a temporary directory.
+    fn site(pages: &[(&str, &str)]) -> tempfile::TempDir {
+        let site = tempfile::tempdir().unwrap();
+        for (rel, page) in pages {
+            fsutil::write_file(&site.path().join(rel), page).unwrap();
+        }
+        site
+    }
+
+    #[test]
+    fn finds_nothing_in_a_whole_site() {
+        let site = site(&[
+            ("index.html", r#"<a href="alpha/index.html">alpha</a>"#),
+            (
+                "alpha/index.html",
+                r#"<a href="struct.Thing.html#method.new">new</a> <a href="https://docs.rs/">"#,
+            ),
+            ("alpha/struct.Thing.html", r#"<section id="method.new">"#),
+        ]);
+        let report = check(site.path()).unwrap();
+        assert_eq!((report.pages, report.links), (3, 2));
+        assert_eq!(report.broken_count(), 0);
+    }
+
+    #[test]
+    fn finds_missing_files_and_anchors() {
+        let site = site(&[
+            (
+                "alpha/index.html",
+                r#"<a href="struct.Gone.html">gone</a> <a href="struct.Thing.html#method.gone">"#,
+            ),
+            ("alpha/struct.Thing.html", r#"<section id="method.new">"#),
+        ]);
+        let report = check(site.path()).unwrap();
+        assert_eq!(
+            report.broken["alpha"],
+            [
+                BrokenLink {
+                    page: PathBuf::from("alpha/index.html"),
+                    href: "struct.Gone.html".to_owned(),
+                    problem: LinkProblem::MissingFile,
+                },
+                BrokenLink {
+                    page: PathBuf::from("alpha/index.html"),
+                    href: "struct.Thing.html#method.gone".to_owned(),
+                    problem: LinkProblem::MissingAnchor,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn groups_broken_links_by_crate() {
+        let site = site(&[
+            ("index.html", r#"<link href="static.files/gone.css">"#),
+            ("alpha/index.html", r#"<a href="../beta/gone/index.html">"#),
+            (
+                "beta/index.html",
+                r#"<a href="fn.gone.html"> <a href="fn.gone.html">"#,
+            ),
+            (
+                "src/beta/lib.rs.html",
+                r#"<a href="../../beta/struct.Gone.html">"#,
+            ),
+            (".docs.previous/index.html", r#"<a href="gone.html">"#),
+        ]);
+        let report = check(site.path()).unwrap();
+        let broken = report
+            .broken
+            .iter()
+            .map(|(crate_name, links)| {
+                let pages = links.iter().map(|link| link.page.to_str().unwrap());
+                (crate_name.as_str(), pages.collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            broken,
+            [
+                ("", vec!["index.html"]),
+                ("alpha", vec!["alpha/index.html"]),
+                ("beta", vec!["beta/index.html", "src/beta/lib.rs.html"]),
+            ]
+        );
+        assert_eq!(report.pages, 4);
+        assert_eq!(report.broken_count(), 4);
+    }
+
+    #[test]
+    fn resolve_leaves_other_sites() {
+        let (dir, page) = (Path::new("/site/beta"), Path::new("/site/beta/index.html"));
+        for href in [
+            "",
+            "https://docs.rs/alpha",
+            "//example.com/page.html",
+            "mailto:someone@example.com",
+            "javascript:void(0)",
+            "?search=thing",
+        ] {
+            assert_eq!(resolve(dir, page, href), None, "{href}");
+        }
+    }
+
+    #[test]
+    fn resolve_relative_links() {
+        let (dir, page) = (Path::new("/site/beta"), Path::new("/site/beta/index.html"));
+        assert_eq!(
+            resolve(dir, page, "../alpha/struct.Thing.html#method.new"),
+            Some((
+                PathBuf::from("/site/alpha/struct.Thing.html"),
+                Some("method.new")
+            ))
+        );
+        assert_eq!(
+            resolve(dir, page, "./fn.beta_fn.html?search=x#"),
+            Some((PathBuf::from("/site/beta/fn.beta_fn.html"), None))
+        );
+        assert_eq!(
+            resolve(dir, page, "inner/"),
+            Some((PathBuf::from("/site/beta/inner/index.html"), None))
+        );
+        assert_eq!(
+            resolve(dir, page, "struct.Wrapper%3CT%3E.html"),
+            Some((PathBuf::from("/site/beta/struct.Wrapper<T>.html"), None))
+        );
+    }
+
+    #[test]
+    fn resolve_fragments_within_the_page() {
+        let (dir, page) = (Path::new("/site/beta"), Path::new("/site/beta/index.html"));
+        assert_eq!(
+            resolve(dir, page, "#structs"),
+            Some((page.to_owned(), Some("structs")))
+        );
+        assert_eq!(resolve(dir, page, "#"), None);
+    }
+
+    #[test]
+    fn resolve_directories_to_their_index() {
+        let site = tempfile::tempdir().unwrap();
+        fs::create_dir_all(site.path().join("beta/inner")).unwrap();
+        let dir = site.path().join("beta");
+        assert_eq!(
+            resolve(&dir, &dir.join("index.html"), "inner#modules"),
+            Some((dir.join("inner/index.html"), Some("modules")))
+        );
+        assert_eq!(
+            resolve(&dir, &dir.join("index.html"), ".."),
+            Some((site.path().join("index.html"), None))
+        );
+    }
+
+    #[test]
+    fn percent_decode_escapes() {
+        assert_eq!(
+            percent_decode("impl-From%3CT%3E-for-U"),
+            "impl-From<T>-for-U"
+        );
+        assert_eq!(percent_decode("%E2%9C%93%20done"), "✓ done");
+        assert_eq!(percent_decode("plain.html"), "plain.html");
+    }
+
+    #[test]
+    fn percent_decode_keeps_invalid_escapes() {
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+        assert_eq!(percent_decode("%%41"), "%A");
+        assert_eq!(percent_decode("%FF"), "\u{FFFD}");
+    }
+}
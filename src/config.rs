@@ -0,0 +1,325 @@
+//! The `doc-merge.toml` configuration file.
+//!
+//! ```toml
+//! sources = ["../api/target/doc", "../client/target/doc"]
+//! workspaces = ["../server"]
+//! build = true
+//! dest = "docs"
+//! title = "Acme API documentation"
+//! logo = "https://acme.example/logo.svg"
+//! exclude = ["serde*"]
+//! on_conflict = "error"
+//! ```
+//!
+//! Relative paths are resolved against the directory containing the file.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::doc_build::DocBuild;
+use crate::filter::Glob;
+use crate::landing;
+use crate::workspace::Workspace;
+use crate::{ConflictPolicy, Latest, LinkMode, Merger, Prune, Unchanged, VersionMismatch};
+
+/// The settings of a merge, as read from a `doc-merge.toml` file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// The documentation directories to merge.
+    #[serde(default)]
+    pub sources: Vec<PathBuf>,
+
+    /// Cargo workspaces whose members' documentation is merged.
+    #[serde(default)]
+    pub workspaces: Vec<PathBuf>,
+
+    /// Only merge the workspace members matching one of these globs.
+    #[serde(default)]
+    pub members: Vec<String>,
+
+    /// Run `cargo doc` for every workspace before merging.
+    #[serde(default)]
+    pub build: bool,
+
+    /// Features to enable when running `cargo doc`.
+    #[serde(default)]
+    pub features: Vec<String>,
+
+    /// The target triple the workspaces are documented for.
+    pub target: Option<String>,
+
+    /// Extra flags for rustdoc when running `cargo doc`.
+    pub rustdocflags: Option<String>,
+
+    /// The root of the shared rustdoc site.
+    pub dest: Option<PathBuf>,
+
+    /// The version of the documentation, merged into its own directory of the site next to the
+    /// other versions.
+    pub doc_version: Option<String>,
+
+    /// Keep a `latest` alias pointing at the newest version in the site, in this way.
+    pub latest: Option<Latest>,
+
+    /// Crate and module paths that moved, such as `old_crate = "new_crate"`, mapped to their new
+    /// paths.
+    #[serde(default)]
+    pub redirects: BTreeMap<String, String>,
+
+    /// Redirect from the pages of crates that were renamed since the last merge, by comparing
+    /// the site's search index before and after.
+    #[serde(default)]
+    pub detect_moves: bool,
+
+    /// Rewrite links to the docs.rs pages of the merged crates into links to the site's pages.
+    #[serde(default)]
+    pub local_links: bool,
+
+    /// The crate the root index.html redirects to, instead of listing every crate.
+    pub index_crate: Option<String>,
+
+    /// The title of the landing page.
+    pub title: Option<String>,
+
+    /// The image shown next to the title of the landing page, as a URL relative to the page.
+    pub logo: Option<String>,
+
+    /// The icon of the landing page, as a URL relative to the page.
+    pub favicon: Option<String>,
+
+    /// Stylesheets the landing page loads after rustdoc's, as URLs relative to the page.
+    #[serde(default)]
+    pub stylesheets: Vec<String>,
+
+    /// Only merge crates matching one of these globs.
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Never merge crates matching one of these globs.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    /// What to do when more than one source documents the same crate.
+    pub on_conflict: Option<ConflictPolicy>,
+
+    /// What to do when the sources were documented by different versions of rustdoc.
+    pub on_version_mismatch: Option<VersionMismatch>,
+
+    /// Whether to list or delete the files in the destination that the merge did not produce.
+    pub prune: Option<Prune>,
+
+    /// How many files to copy at the same time.
+    pub jobs: Option<usize>,
+
+    /// How files with the same contents are written to the merged site.
+    pub link_mode: Option<LinkMode>,
+
+    /// Leave the files that the merge would not change alone, comparing them this way.
+    pub skip_unchanged: Option<Unchanged>,
+}
+
+impl Config {
+    /// The file that is read when no configuration file is given explicitly.
+    pub const DEFAULT_PATH: &'static str = "doc-merge.toml";
+
+    /// The destination used when neither the configuration nor the command line sets one.
+    pub const DEFAULT_DEST: &'static str = "./docs";
+
+    /// The title of the landing page when the configuration does not set one.
+    pub const DEFAULT_TITLE: &'static str = landing::DEFAULT_TITLE;
+
+    /// Read a configuration file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        let mut config: Self =
+            toml::from_str(&content).with_context(|| format!("Invalid {}", path.display()))?;
+
+        let base = path.parent().unwrap_or(Path::new(""));
+        for src in &mut config.sources {
+            *src = base.join(&*src);
+        }
+        for workspace in &mut config.workspaces {
+            *workspace = base.join(&*workspace);
+        }
+        if let Some(dest) = &mut config.dest {
+            *dest = base.join(&*dest);
+        }
+        Ok(config)
+    }
+
+    /// Build a [`Merger`] with these settings.
+    pub fn into_merger(self) -> Result<Merger> {
+        // these only apply to workspaces, which may come from the file or the command line
+        if self.workspaces.is_empty() && !self.members.is_empty() {
+            bail!("`members` can only be set along with the workspaces to merge");
+        }
+        if self.workspaces.is_empty() && self.target.is_some() {
+            bail!("`target` can only be set along with the workspaces to merge");
+        }
+        if self.workspaces.is_empty() && self.build {
+            bail!("`build` can only be set along with the workspaces to merge");
+        }
+        if !self.build && !self.features.is_empty() {
+            bail!("`features` can only be set along with `build`");
+        }
+        if !self.build && self.rustdocflags.is_some() {
+            bail!("`rustdocflags` can only be set along with `build`");
+        }
+        let mut merger = Merger::new(self.dest.unwrap_or_else(|| Self::DEFAULT_DEST.into()))
+            .sources(self.sources)
+            .on_conflict(self.on_conflict.unwrap_or_default());
+        let members = self
+            .members
+            .iter()
+            .map(|pattern| Glob::new(pattern))
+            .collect::<Result<Vec<_>>>()?;
+        for path in &self.workspaces {
+            let mut workspace = Workspace::discover(path)?;
+            workspace.retain_matching(&members);
+            workspace.target.clone_from(&self.target);
+            merger = merger.workspace(workspace);
+        }
+        if self.build {
+            merger = merger.build(DocBuild {
+                features: self.features,
+                rustdocflags: self.rustdocflags,
+            });
+        }
+        if let Some(policy) = self.on_version_mismatch {
+            merger = merger.on_version_mismatch(policy);
+        }
+        if let Some(unchanged) = self.skip_unchanged {
+            merger = merger.skip_unchanged(unchanged);
+        }
+        if let Some(link_mode) = self.link_mode {
+            merger = merger.link_mode(link_mode);
+        }
+        if let Some(jobs) = self.jobs {
+            merger = merger.jobs(jobs);
+        }
+        if let Some(prune) = self.prune {
+            merger = merger.prune(prune);
+        }
+        if let Some(version) = self.doc_version {
+            merger = merger.version(version);
+        }
+        if let Some(latest) = self.latest {
+            merger = merger.latest(latest);
+        }
+        for (from, to) in self.redirects {
+            merger = merger.redirect(from, to);
+        }
+        if self.detect_moves {
+            merger = merger.detect_moves(true);
+        }
+        if self.local_links {
+            merger = merger.local_links(true);
+        }
+        if let Some(index_crate) = self.index_crate {
+            merger = merger.index_crate(index_crate);
+        }
+        if let Some(title) = self.title {
+            merger = merger.title(title);
+        }
+        if let Some(logo) = self.logo {
+            merger = merger.logo(logo);
+        }
+        if let Some(favicon) = self.favicon {
+            merger = merger.favicon(favicon);
+        }
+        for stylesheet in self.stylesheets {
+            merger = merger.stylesheet(stylesheet);
+        }
+        for pattern in &self.include {
+            merger = merger.include(Glob::new(pattern)?);
+        }
+        for pattern in &self.exclude {
+            merger = merger.exclude(Glob::new(pattern)?);
+        }
+        Ok(merger)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write `content` as the `doc-merge.toml` of a new directory, and read it.
+    fn load(content: &str) -> (tempfile::TempDir, Result<Config>) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(Config::DEFAULT_PATH);
+        fs::write(&path, content).unwrap();
+        let config = Config::load(&path);
+        (dir, config)
+    }
+
+    #[test]
+    fn reads_every_setting() {
+        let (_dir, config) = load(
+            r#"
+            title = "Acme API documentation"
+            exclude = ["serde*"]
+            on_conflict = "first-wins"
+            on_version_mismatch = "rewrite"
+            prune = "dry-run"
+            link_mode = "hardlink"
+            skip_unchanged = "content"
+            latest = "redirect"
+            doc_version = "1.2"
+            jobs = 4
+            detect_moves = true
+            redirects = { old_crate = "new_crate" }
+            "#,
+        );
+        let config = config.unwrap();
+        assert_eq!(config.title.as_deref(), Some("Acme API documentation"));
+        assert_eq!(config.exclude, ["serde*"]);
+        assert_eq!(config.on_conflict, Some(ConflictPolicy::FirstWins));
+        assert_eq!(config.on_version_mismatch, Some(VersionMismatch::Rewrite));
+        assert_eq!(config.prune, Some(Prune::DryRun));
+        assert_eq!(config.link_mode, Some(LinkMode::Hardlink));
+        assert_eq!(config.skip_unchanged, Some(Unchanged::Content));
+        assert_eq!(config.latest, Some(Latest::Redirect));
+        assert_eq!(config.doc_version.as_deref(), Some("1.2"));
+        assert_eq!(config.jobs, Some(4));
+        assert!(config.detect_moves);
+        assert!(!config.local_links && !config.build);
+        assert_eq!(
+            config.redirects,
+            BTreeMap::from([("old_crate".to_owned(), "new_crate".to_owned())])
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_settings_and_values() {
+        assert!(load("on_conflict = \"newest\"").1.is_err());
+        assert!(load("source = [\"target/doc\"]").1.is_err());
+    }
+
+    #[test]
+    fn resolves_paths_against_the_directory_of_the_file() {
+        let (dir, config) = load(
+            r#"
+            sources = ["../api/target/doc", "/srv/client/doc"]
+            workspaces = ["server"]
+            dest = "docs"
+            "#,
+        );
+        let config = config.unwrap();
+        assert_eq!(
+            config.sources,
+            [
+                dir.path().join("../api/target/doc"),
+                PathBuf::from("/srv/client/doc")
+            ]
+        );
+        assert_eq!(config.workspaces, [dir.path().join("server")]);
+        assert_eq!(config.dest, Some(dir.path().join("docs")));
+    }
+}
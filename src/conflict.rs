@@ -0,0 +1,103 @@
+//! Resolution of crates that appear in more than one source.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::MergeError;
+
+/// What to do when the same crate is documented by more than one source.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConflictPolicy {
+    /// Refuse to merge.
+    Error,
+
+    /// Use the crate from the first source that documents it.
+    FirstWins,
+
+    /// Use the crate from the last source that documents it.
+    #[default]
+    LastWins,
+
+    /// Use the crate from the source whose documentation of it was built most recently.
+    PreferNewest,
+}
+
+named_options!(ConflictPolicy, "conflict policy", {
+    Error => "error",
+    FirstWins => "first-wins",
+    LastWins => "last-wins",
+    PreferNewest => "prefer-newest",
+});
+
+impl ConflictPolicy {
+    /// Pick the source to take `crate_name` from, out of every source that documents it.
+    ///
+    /// `candidates` is in the order the sources were given, and must not be empty.
+    pub(crate) fn resolve(self, crate_name: &str, candidates: &[PathBuf]) -> Result<PathBuf> {
+        let chosen = match self {
+            _ if candidates.len() == 1 => candidates.first(),
+            Self::Error => {
+                return Err(MergeError::Conflict {
+                    crate_name: crate_name.to_owned(),
+                    sources: candidates.to_vec(),
+                }
+                .into())
+            }
+            Self::FirstWins => candidates.first(),
+            Self::LastWins => candidates.last(),
+            // `max_by_key` returns the last maximum, so ties fall back to last-wins.
+            Self::PreferNewest => candidates
+                .iter()
+                .max_by_key(|src| built_at(src, crate_name)),
+        };
+        Ok(chosen.expect("at least one candidate").clone())
+    }
+}
+
+/// A crate that was documented by more than one source.
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    /// The name of the crate.
+    pub crate_name: String,
+
+    /// Every source that documents the crate, in the order they were given.
+    pub sources: Vec<PathBuf>,
+
+    /// The source the crate was taken from.
+    pub chosen: PathBuf,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "crate `{}` is documented by {}; using {}",
+            self.crate_name,
+            display_paths(&self.sources),
+            self.chosen.display()
+        )
+    }
+}
+
+/// When the documentation of `crate_name` in `src` was last written.
+fn built_at(src: &Path, crate_name: &str) -> Option<SystemTime> {
+    let dir = src.join(crate_name);
+    fs::metadata(dir.join("index.html"))
+        .or_else(|_| fs::metadata(&dir))
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+pub(crate) fn display_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,89 @@
+//! Running `cargo doc` for each workspace before merging.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+use crate::workspace::Workspace;
+
+/// How many lines of `cargo doc` output to show for a failed build.
+const STDERR_TAIL_LINES: usize = 20;
+
+/// How `cargo doc` is run for each workspace before merging.
+#[derive(Debug, Clone, Default)]
+pub struct DocBuild {
+    /// Features to enable, passed to `cargo doc --features`.
+    pub features: Vec<String>,
+
+    /// Extra flags for rustdoc, passed in the `RUSTDOCFLAGS` environment variable.
+    pub rustdocflags: Option<String>,
+}
+
+/// A workspace whose documentation failed to build.
+#[derive(Debug, Clone)]
+pub struct BuildFailure {
+    /// The root of the workspace.
+    pub root: PathBuf,
+
+    /// What `cargo doc` printed to stderr.
+    pub stderr: String,
+}
+
+impl DocBuild {
+    /// Run `cargo doc --workspace --no-deps` for `workspace`.
+    pub fn run(&self, workspace: &Workspace) -> Result<Option<BuildFailure>> {
+        let mut cargo = Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+        cargo
+            .args(["doc", "--workspace", "--no-deps"])
+            .current_dir(&workspace.root);
+        if !self.features.is_empty() {
+            cargo.arg("--features").arg(self.features.join(","));
+        }
+        if let Some(target) = &workspace.target {
+            cargo.arg("--target").arg(target);
+        }
+        if let Some(rustdocflags) = &self.rustdocflags {
+            cargo.env("RUSTDOCFLAGS", rustdocflags);
+        }
+
+        let output = cargo.output().with_context(|| {
+            format!("Could not run `cargo doc` in {}", workspace.root.display())
+        })?;
+        if output.status.success() {
+            return Ok(None);
+        }
+        Ok(Some(BuildFailure {
+            root: workspace.root.clone(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        }))
+    }
+
+    /// Build every workspace, failing with the output of each build that did not succeed.
+    pub(crate) fn run_all(&self, workspaces: &[Workspace]) -> Result<()> {
+        let mut failures = Vec::new();
+        for workspace in workspaces {
+            failures.extend(self.run(workspace)?);
+        }
+        if failures.is_empty() {
+            return Ok(());
+        }
+        bail!(
+            "`cargo doc` failed for {} of {} workspaces:\n\n{}",
+            failures.len(),
+            workspaces.len(),
+            failures
+                .iter()
+                .map(|failure| {
+                    let lines = failure.stderr.trim_end().lines().collect::<Vec<_>>();
+                    format!(
+                        "{}:\n{}",
+                        failure.root.display(),
+                        lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n")
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        )
+    }
+}
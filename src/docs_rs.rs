@@ -0,0 +1,229 @@
+//! Links to the docs.rs pages of crates that are part of the merged site.
+//!
+//! A crate documented on its own links to the crates it depends on at docs.rs. Once those crates
+//! are merged into the same site, the links can lead to the site's own pages instead, which
+//! document the same version of the code and keep working without docs.rs.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use jzon::JsonValue;
+use regex::{Captures, Regex};
+
+/// The regex matching links to docs.rs, for [`localize`].
+pub(crate) fn link_regex() -> Regex {
+    Regex::new(r#"href="https?://docs\.rs/([A-Za-z0-9_-]+)(/[^"]*)?""#).expect("valid regex")
+}
+
+/// Rewrite the links in `page`, found at `rel` in the merged site, that lead to the docs.rs pages
+/// of any of `crates` into relative links to the site's own pages. `links` is the regex from
+/// [`link_regex`]. Returns `None` if the page has no such links.
+///
+/// Links to docs.rs pages that are not part of a crate's documentation, such as the docs.rs
+/// source browser or a crate's list of features, are left alone.
+pub(crate) fn localize(
+    page: &str,
+    rel: &Path,
+    crates: &BTreeMap<String, PathBuf>,
+    links: &Regex,
+) -> Option<String> {
+    let up = "../".repeat(rel.components().count().saturating_sub(1));
+    rewrite(page, &up, crates, links)
+}
+
+/// Rewrite the links to the docs.rs pages of any of `crates` in the implementations listed by a
+/// crate in a `trait.impl/` or `type.impl/` file, once parsed, like [`localize`] does for pages.
+/// rustdoc's scripts resolve the links in these lists against the root of the site, wherever the
+/// page showing them is.
+pub(crate) fn localize_impls(
+    data: &mut JsonValue,
+    crates: &BTreeMap<String, PathBuf>,
+    links: &Regex,
+) {
+    if let Some(html) = data.as_str() {
+        if let Some(html) = rewrite(html, "", crates, links) {
+            *data = html.into();
+        }
+        return;
+    }
+    for member in data.members_mut() {
+        localize_impls(member, crates, links);
+    }
+    for (_, value) in data.entries_mut() {
+        localize_impls(value, crates, links);
+    }
+}
+
+/// Rewrite the links in `html` to the docs.rs pages of any of `crates` into links relative to the
+/// root of the site, which is at `up` from where the links are resolved.
+fn rewrite(
+    html: &str,
+    up: &str,
+    crates: &BTreeMap<String, PathBuf>,
+    links: &Regex,
+) -> Option<String> {
+    if !html.contains("docs.rs/") {
+        return None;
+    }
+    let mut changed = false;
+    let html = links.replace_all(html, |captures: &Captures<'_>| {
+        let crate_name = captures[1].replace('-', "_");
+        let rest = captures.get(2).map_or("", |rest| rest.as_str());
+        let target = if crates.contains_key(&crate_name) {
+            local_path(&crate_name, rest)
+        } else {
+            None
+        };
+        match target {
+            Some(target) => {
+                changed = true;
+                format!("href=\"{up}{target}\"")
+            }
+            None => captures[0].to_owned(),
+        }
+    });
+    changed.then(|| html.into_owned())
+}
+
+/// The page of the merged site, relative to its root, that the docs.rs path `rest` (following
+/// `https://docs.rs/<crate>`) leads to.
+///
+/// docs.rs paths look like `/<version>/<crate>/<item path>`, with the name of a target such as
+/// `x86_64-unknown-linux-gnu` before the crate for documentation that differs between targets,
+/// or `/<version>/src/<crate>/<file>` for the source pages.
+fn local_path(crate_name: &str, rest: &str) -> Option<String> {
+    let (path, fragment) = match rest.find(['#', '?']) {
+        Some(i) if rest[i..].starts_with('#') => rest.split_at(i),
+        Some(i) => (&rest[..i], ""),
+        None => (rest, ""),
+    };
+    let segments = path.split('/').filter(|segment| !segment.is_empty());
+    // skip the version, and then the target if there is one
+    let segments = segments.skip(1).collect::<Vec<_>>();
+    let start = match segments.iter().position(|segment| *segment == crate_name) {
+        // the source pages are in `src/<crate>/`, in the site as on docs.rs
+        Some(1) if segments[0] == "src" => 0,
+        Some(start @ (0 | 1)) => start,
+        None if segments.is_empty() => return Some(format!("{crate_name}/index.html{fragment}")),
+        _ => return None,
+    };
+    let item = &segments[start..];
+    let mut target = item.join("/");
+    if path.ends_with('/') || item.len() == 1 {
+        target.push_str("/index.html");
+    }
+    Some(format!("{target}{fragment}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_path_of_items() {
+        assert_eq!(
+            local_path("alpha", "/0.1.0/alpha/struct.Thing.html").as_deref(),
+            Some("alpha/struct.Thing.html")
+        );
+        assert_eq!(
+            local_path("alpha", "/latest/alpha/inner/enum.Kind.html").as_deref(),
+            Some("alpha/inner/enum.Kind.html")
+        );
+        assert_eq!(
+            local_path("alpha", "/*/alpha/struct.Thing.html#method.new").as_deref(),
+            Some("alpha/struct.Thing.html#method.new")
+        );
+        assert_eq!(
+            local_path("alpha", "/latest/alpha/?search=thing").as_deref(),
+            Some("alpha/index.html")
+        );
+    }
+
+    #[test]
+    fn local_path_of_modules() {
+        assert_eq!(local_path("alpha", "").as_deref(), Some("alpha/index.html"));
+        assert_eq!(
+            local_path("alpha", "/0.1.0").as_deref(),
+            Some("alpha/index.html")
+        );
+        assert_eq!(
+            local_path("alpha", "/0.1.0/alpha").as_deref(),
+            Some("alpha/index.html")
+        );
+        assert_eq!(
+            local_path("alpha", "/0.1.0/alpha/inner/").as_deref(),
+            Some("alpha/inner/index.html")
+        );
+    }
+
+    #[test]
+    fn local_path_skips_the_target() {
+        assert_eq!(
+            local_path(
+                "alpha",
+                "/0.1.0/x86_64-pc-windows-msvc/alpha/fn.add.html#examples"
+            )
+            .as_deref(),
+            Some("alpha/fn.add.html#examples")
+        );
+    }
+
+    #[test]
+    fn local_path_of_source_pages() {
+        assert_eq!(
+            local_path("alpha", "/0.1.0/src/alpha/lib.rs.html#10-12").as_deref(),
+            Some("src/alpha/lib.rs.html#10-12")
+        );
+    }
+
+    #[test]
+    fn local_path_leaves_other_pages() {
+        assert_eq!(local_path("alpha", "/0.1.0/beta/index.html"), None);
+        assert_eq!(local_path("alpha", "/0.1.0/a/b/alpha/index.html"), None);
+    }
+
+    #[test]
+    fn localize_rewrites_links_to_merged_crates() {
+        let crates = BTreeMap::from([("my_crate".to_owned(), PathBuf::from("src"))]);
+        let page = r#"<a href="https://docs.rs/my-crate/1.0.0/my_crate/struct.S.html">S</a> <a href="https://docs.rs/other/1.0.0/other/">other</a>"#;
+        assert_eq!(
+            localize(page, Path::new("beta/index.html"), &crates, &link_regex()).as_deref(),
+            Some(
+                r#"<a href="../my_crate/struct.S.html">S</a> <a href="https://docs.rs/other/1.0.0/other/">other</a>"#
+            )
+        );
+        assert_eq!(
+            localize("no links", Path::new("index.html"), &crates, &link_regex()),
+            None
+        );
+    }
+
+    #[test]
+    fn localize_impls_rewrites_links_to_merged_crates() {
+        let crates = BTreeMap::from([
+            ("alpha".to_owned(), PathBuf::from("alpha-docs")),
+            ("gamma".to_owned(), PathBuf::from("gamma-docs")),
+        ]);
+        // gamma implements a trait of alpha, and a trait of a crate outside the site
+        let mut data = jzon::array![
+            [
+                r#"impl <a class="trait" href="https://docs.rs/alpha/0.1.0/alpha/trait.Shape.html" title="trait alpha::Shape">Shape</a> for <a class="struct" href="gamma/struct.Circle.html" title="struct gamma::Circle">Circle</a>"#,
+                0
+            ],
+            [
+                r#"impl <a class="trait" href="https://docs.rs/serde/1.0.0/serde/trait.Serialize.html" title="trait serde::Serialize">Serialize</a> for <a class="struct" href="gamma/struct.Circle.html" title="struct gamma::Circle">Circle</a>"#,
+                0
+            ]
+        ];
+        localize_impls(&mut data, &crates, &link_regex());
+        assert_eq!(
+            data[0][0].as_str(),
+            Some(
+                r#"impl <a class="trait" href="alpha/trait.Shape.html" title="trait alpha::Shape">Shape</a> for <a class="struct" href="gamma/struct.Circle.html" title="struct gamma::Circle">Circle</a>"#
+            )
+        );
+        assert!(data[1][0]
+            .as_str()
+            .is_some_and(|html| html.contains("https://docs.rs/serde/")));
+    }
+}
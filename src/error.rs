@@ -0,0 +1,197 @@
+//! The ways a merge can fail because of what it found in its sources.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::conflict;
+
+/// An error that stops a merge, naming the source file or directory (and the crate, where there
+/// is one) that caused it.
+///
+/// Errors that are not about the sources, such as a missing command-line option or a failed
+/// `cargo doc`, are reported as plain [`anyhow::Error`]s instead. Use
+/// [`anyhow::Error::downcast_ref`] to tell the two apart.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MergeError {
+    /// A source has no rustdoc search index.
+    MissingSearchIndex {
+        /// The source directory.
+        src: PathBuf,
+    },
+
+    /// A search index is in a format that cannot be merged, or in a different format than the
+    /// other sources.
+    UnsupportedFormat {
+        /// The search index.
+        path: PathBuf,
+
+        /// The name of the format, as given by [`SearchIndexFormat::name`].
+        ///
+        /// [`SearchIndexFormat::name`]: crate::search_index::SearchIndexFormat::name
+        format: &'static str,
+
+        /// Why the format cannot be used.
+        reason: String,
+    },
+
+    /// A file that rustdoc wrote could not be parsed, or does not match the rest of its source.
+    InvalidFile {
+        /// The file.
+        path: PathBuf,
+
+        /// The crate whose data is invalid, if the problem is limited to one crate.
+        crate_name: Option<String>,
+
+        /// What is wrong with the file.
+        reason: String,
+    },
+
+    /// A file name in a source is not valid UTF-8, which rustdoc never writes.
+    NonUtf8Path {
+        /// The file.
+        path: PathBuf,
+    },
+
+    /// A crate is documented by more than one source, and conflicts are errors.
+    Conflict {
+        /// The crate.
+        crate_name: String,
+
+        /// Every source that documents the crate.
+        sources: Vec<PathBuf>,
+    },
+
+    /// The sources were documented by different versions of rustdoc, and that is an error.
+    VersionMismatch {
+        /// The version of rustdoc each source was documented with.
+        versions: BTreeMap<PathBuf, String>,
+    },
+
+    /// Reading or writing a file failed.
+    Io {
+        /// The file or directory.
+        path: PathBuf,
+
+        /// What went wrong.
+        error: io::Error,
+    },
+}
+
+impl MergeError {
+    /// The exit code the command-line tool reports this error with.
+    ///
+    /// Other errors exit with 1, and invalid command-line arguments with 2.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::MissingSearchIndex { .. } => 3,
+            Self::UnsupportedFormat { .. } => 4,
+            Self::InvalidFile { .. } => 5,
+            Self::NonUtf8Path { .. } => 6,
+            Self::Conflict { .. } => 7,
+            Self::Io { .. } => 8,
+            Self::VersionMismatch { .. } => 9,
+        }
+    }
+
+    /// Wrap an I/O error on `path`, for use with [`Result::map_err`].
+    pub(crate) fn io(path: &Path) -> impl FnOnce(io::Error) -> Self + '_ {
+        move |error| Self::Io {
+            path: path.to_owned(),
+            error,
+        }
+    }
+
+    /// Name the paths below `from` by where they end up below `to` instead, for errors found
+    /// while merging into a staging directory.
+    pub(crate) fn relocate(&mut self, from: &Path, to: &Path) {
+        let moved = |path: &mut PathBuf| {
+            if let Ok(rel) = path.strip_prefix(from) {
+                *path = if rel.as_os_str().is_empty() {
+                    to.to_owned()
+                } else {
+                    to.join(rel)
+                };
+            }
+        };
+        match self {
+            Self::MissingSearchIndex { src: path }
+            | Self::UnsupportedFormat { path, .. }
+            | Self::InvalidFile { path, .. }
+            | Self::NonUtf8Path { path }
+            | Self::Io { path, .. } => moved(path),
+            Self::Conflict { sources, .. } => sources.iter_mut().for_each(moved),
+            Self::VersionMismatch { versions } => {
+                *versions = std::mem::take(versions)
+                    .into_iter()
+                    .map(|(mut src, version)| {
+                        moved(&mut src);
+                        (src, version)
+                    })
+                    .collect();
+            }
+        }
+    }
+
+    /// A file at `path` that could not be parsed.
+    pub(crate) fn invalid(path: &Path, reason: impl Into<String>) -> Self {
+        Self::InvalidFile {
+            path: path.to_owned(),
+            crate_name: None,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSearchIndex { src } => write!(
+                f,
+                "No rustdoc search index found in {}; is it a `target/doc` directory that \
+                 `cargo doc` has been run for?",
+                src.display()
+            ),
+            Self::UnsupportedFormat { path, reason, .. } => {
+                write!(f, "{}: {reason}", path.display())
+            }
+            Self::InvalidFile {
+                path,
+                crate_name: Some(crate_name),
+                reason,
+            } => write!(f, "{}: {reason} (crate `{crate_name}`)", path.display()),
+            Self::InvalidFile { path, reason, .. } => write!(f, "{}: {reason}", path.display()),
+            Self::NonUtf8Path { path } => write!(
+                f,
+                "{}: file names in documentation must be valid UTF-8",
+                path.display()
+            ),
+            Self::Conflict {
+                crate_name,
+                sources,
+            } => write!(
+                f,
+                "Crate `{crate_name}` is documented by more than one source: {}; choose a \
+                 conflict policy other than `error` to merge it anyway",
+                conflict::display_paths(sources)
+            ),
+            Self::VersionMismatch { versions } => write!(
+                f,
+                "The sources were documented by different versions of rustdoc: {}; document \
+                 them all with the same toolchain, or choose a version mismatch policy other \
+                 than `error` to merge them anyway",
+                versions
+                    .iter()
+                    .map(|(src, version)| format!("{} ({version})", src.display()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::Io { path, error } => write!(f, "{}: {error}", path.display()),
+        }
+    }
+}
+
+impl Error for MergeError {}
@@ -0,0 +1,64 @@
+//! Glob filters for choosing which crates end up in the merged site.
+
+use anyhow::Result;
+use regex::Regex;
+
+/// A shell-style glob pattern, such as `my_crate_*`.
+///
+/// `*` matches any run of characters and `?` matches a single character; everything else matches
+/// itself.
+#[derive(Debug, Clone)]
+pub struct Glob {
+    pattern: String,
+    regex: Regex,
+}
+
+impl Glob {
+    /// Compile a glob pattern.
+    pub fn new(pattern: &str) -> Result<Self> {
+        let regex = regex::escape(pattern)
+            .replace(r"\*", ".*")
+            .replace(r"\?", ".");
+        Ok(Self {
+            pattern: pattern.to_owned(),
+            regex: Regex::new(&format!("^{regex}$"))?,
+        })
+    }
+
+    /// The pattern this glob was compiled from.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Whether `name` matches the pattern.
+    pub fn matches(&self, name: &str) -> bool {
+        self.regex.is_match(name)
+    }
+}
+
+/// Include and exclude globs, applied to crate names.
+///
+/// A crate is kept if it matches any include pattern (or there are none), and no exclude pattern.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CrateFilter {
+    include: Vec<Glob>,
+    exclude: Vec<Glob>,
+}
+
+impl CrateFilter {
+    /// Only keep crates matching `glob` (or another include pattern).
+    pub(crate) fn include(&mut self, glob: Glob) {
+        self.include.push(glob);
+    }
+
+    /// Drop crates matching `glob`.
+    pub(crate) fn exclude(&mut self, glob: Glob) {
+        self.exclude.push(glob);
+    }
+
+    /// Whether the crate called `name` should be merged.
+    pub(crate) fn allows(&self, name: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|glob| glob.matches(name)))
+            && !self.exclude.iter().any(|glob| glob.matches(name))
+    }
+}
@@ -0,0 +1,151 @@
+//! Filesystem helpers shared by the merge steps.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+
+use anyhow::Result;
+
+use crate::{MergeError, Unchanged};
+
+/// List every file below `root`, as paths relative to `root`, in a stable order.
+pub(crate) fn walk_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![PathBuf::new()];
+    while let Some(dir) = dirs.pop() {
+        let path = root.join(&dir);
+        for entry in fs::read_dir(&path).map_err(MergeError::io(&path))? {
+            let entry = entry.map_err(MergeError::io(&path))?;
+            let rel = dir.join(entry.file_name());
+            if entry.file_type().map_err(MergeError::io(&path))?.is_dir() {
+                dirs.push(rel);
+            } else {
+                files.push(rel);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Write `contents` to `path`, creating its parent directories as needed.
+pub(crate) fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    prepare(path)?;
+    fs::write(path, contents).map_err(MergeError::io(path))?;
+    Ok(())
+}
+
+/// Copy the file at `from` to `to`, creating its parent directories as needed. Returns the number
+/// of bytes copied.
+///
+/// The copy keeps the modification time of `from`, so that later merges can tell it is
+/// unchanged.
+pub(crate) fn copy_file(from: &Path, to: &Path) -> Result<u64> {
+    prepare(to)?;
+    let bytes = fs::copy(from, to).map_err(|err| {
+        // name the file that could not be read, or else the one that could not be written
+        let path = if fs::File::open(from).is_ok() {
+            to
+        } else {
+            from
+        };
+        MergeError::io(path)(err)
+    })?;
+    if let Ok(modified) = fs::metadata(from).and_then(|meta| meta.modified()) {
+        fs::File::options()
+            .write(true)
+            .open(to)
+            .and_then(|file| file.set_modified(modified))
+            .map_err(MergeError::io(to))?;
+    }
+    Ok(bytes)
+}
+
+/// What [`copy_files`] did.
+#[derive(Debug, Default)]
+pub(crate) struct Copied {
+    /// The number of bytes copied.
+    pub(crate) bytes: u64,
+
+    /// The number of files copied.
+    pub(crate) files: usize,
+
+    /// The number of files that were not copied because they were unchanged.
+    pub(crate) skipped: usize,
+}
+
+/// Copy each `(from, to)` pair of files on `jobs` threads, leaving out the files that `skip`
+/// finds unchanged.
+///
+/// No two pairs may have the same `to`, since the order they are copied in is not defined.
+pub(crate) fn copy_files(
+    files: &[(PathBuf, PathBuf)],
+    jobs: usize,
+    skip: Option<Unchanged>,
+) -> Result<Copied> {
+    let next = AtomicUsize::new(0);
+    let failed = AtomicBool::new(false);
+    thread::scope(|scope| {
+        let workers = (0..jobs.clamp(1, files.len().max(1)))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut copied = Copied::default();
+                    while !failed.load(Ordering::Relaxed) {
+                        let Some((from, to)) = files.get(next.fetch_add(1, Ordering::Relaxed))
+                        else {
+                            break;
+                        };
+                        let outcome = match skip {
+                            Some(skip) => skip.is_unchanged(from, to),
+                            None => Ok(false),
+                        }
+                        .and_then(|unchanged| {
+                            if unchanged {
+                                Ok(None)
+                            } else {
+                                copy_file(from, to).map(Some)
+                            }
+                        });
+                        match outcome {
+                            Ok(Some(bytes)) => {
+                                copied.bytes += bytes;
+                                copied.files += 1;
+                            }
+                            Ok(None) => copied.skipped += 1,
+                            Err(err) => {
+                                failed.store(true, Ordering::Relaxed);
+                                return Err(err);
+                            }
+                        }
+                    }
+                    Ok(copied)
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut total = Copied::default();
+        for worker in workers {
+            let copied = worker.join().expect("copy worker panicked")?;
+            total.bytes += copied.bytes;
+            total.files += copied.files;
+            total.skipped += copied.skipped;
+        }
+        Ok(total)
+    })
+}
+
+/// Get ready to write the file at `path`: create its parent directories, and unlink the file that
+/// is there, if any.
+///
+/// The staged site shares its files with the live one through hard links, so files must be
+/// replaced rather than written to in place.
+pub(crate) fn prepare(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(MergeError::io(parent))?;
+    }
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(MergeError::io(path)(err).into()),
+        _ => Ok(()),
+    }
+}
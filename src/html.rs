@@ -0,0 +1,38 @@
+//! Helpers for reading and writing the HTML pages of a rustdoc site.
+
+use regex::Regex;
+
+/// Escape text for use in HTML, in element content as well as in attribute values.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Undo [`escape`].
+pub(crate) fn unescape(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&gt;", ">")
+        .replace("&lt;", "<")
+        .replace("&amp;", "&")
+}
+
+/// The (still escaped) content of the `<meta name="...">` tag called `name`, if the page has one.
+pub(crate) fn meta_content(page: &str, name: &str) -> Option<String> {
+    let regex = Regex::new(&format!(
+        r#"<meta name="{}" content="([^"]*)""#,
+        regex::escape(name)
+    ))
+    .expect("valid regex");
+    regex.captures(page).map(|captures| captures[1].to_owned())
+}
+
+/// A page that immediately sends the reader on to `url`.
+pub(crate) fn redirect_page(url: &str) -> String {
+    format!(
+        include_str!("./templates/redirect.html"),
+        url = escape(url),
+        js_url = url.replace('\\', "\\\\").replace('"', "\\\""),
+    )
+}
@@ -0,0 +1,389 @@
+//! Merging of the cross-crate implementor files in `trait.impl/` and `type.impl/`.
+//!
+//! rustdoc writes one file per trait (or type alias) listing the implementations found in each
+//! documented crate. Every source only knows about its own crates, so these files are parsed and
+//! unioned per crate instead of being copied over one another.
+
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use jzon::JsonValue;
+use regex::Regex;
+
+use crate::{docs_rs, fsutil, js, MergeError, MergeReport};
+
+/// The directories holding implementor files. Before rustdoc 1.76, `trait.impl/` was called
+/// `implementors/`.
+pub(crate) const DIRS: &[&str] = &["trait.impl", "type.impl", "implementors"];
+
+/// A parsed implementor file.
+///
+/// The JavaScript around the data is kept as it was found, so that the merged file looks like
+/// whichever rustdoc version wrote it.
+#[derive(Debug)]
+pub(crate) struct ImplFile {
+    /// Everything up to and including `var implementors = ` (or `type_impls`).
+    head: String,
+
+    /// Whether the data is an `Object.fromEntries([...])` call rather than an object literal.
+    from_entries: bool,
+
+    /// Everything after the data, up to the fragment comment.
+    tail: String,
+
+    /// Whether the file ended with a `//{"start":..}` fragment comment.
+    fragments: bool,
+
+    /// The implementations listed by each crate.
+    pub(crate) crates: BTreeMap<String, JsonValue>,
+}
+
+impl ImplFile {
+    /// Parse the implementor file at `path`.
+    pub(crate) fn read(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(MergeError::io(path))?;
+        let invalid = || MergeError::invalid(path, "not a rustdoc implementor file");
+
+        let head = Regex::new(r"(?:var|const|let)\s+(?:implementors|type_impls)\s*=\s*")?
+            .find(&content)
+            .ok_or_else(invalid)?;
+        let mut data_start = head.end();
+        let from_entries = content[data_start..].starts_with("Object.fromEntries(");
+        if from_entries {
+            data_start += "Object.fromEntries(".len();
+        }
+        let register = data_start
+            + content[data_start..]
+                .find("if (window.register_")
+                .ok_or_else(invalid)?;
+        let mut data = content[data_start..register].trim_end();
+        data = data.strip_suffix(';').unwrap_or(data).trim_end();
+        if from_entries {
+            data = data.strip_suffix(')').ok_or_else(invalid)?;
+        }
+        let tail_start = data_start + data.len() + usize::from(from_entries);
+        let (tail_end, fragments) = match content.rfind("//{\"start\"") {
+            Some(i) if i > register => (i, true),
+            _ => (content.len(), false),
+        };
+
+        let json = jzon::parse(data)
+            .map_err(|err| MergeError::invalid(path, format!("invalid JSON: {err}")))?;
+        let crates = if from_entries {
+            json.members()
+                .map(|item| {
+                    let name = item[0].as_str().ok_or_else(invalid)?;
+                    Ok((name.to_owned(), item[1].clone()))
+                })
+                .collect::<Result<_>>()?
+        } else {
+            json.entries()
+                .map(|(name, data)| (name.to_owned(), data.clone()))
+                .collect()
+        };
+
+        Ok(Self {
+            head: content[..head.end()].to_owned(),
+            from_entries,
+            tail: content[tail_start..tail_end].to_owned(),
+            fragments,
+            crates,
+        })
+    }
+
+    /// Write the implementor file to `path`.
+    pub(crate) fn write(&self, path: &Path) -> Result<()> {
+        let fragments = self
+            .crates
+            .iter()
+            .map(|(name, data)| {
+                if self.from_entries {
+                    jzon::array![name.as_str(), data.clone()].dump()
+                } else {
+                    format!("{}:{}", JsonValue::from(name.as_str()).dump(), data.dump())
+                }
+            })
+            .collect::<Vec<_>>();
+        let (open, close) = if self.from_entries {
+            ("Object.fromEntries([", "])")
+        } else {
+            ("{", "}")
+        };
+        let (body, comment) = js::join_fragments(self.head.len() + open.len(), &fragments);
+
+        let mut out = format!("{}{open}{body}{close}{}", self.head, self.tail);
+        if self.fragments {
+            out.push_str(&comment);
+        }
+        fsutil::write_file(path, out)
+    }
+}
+
+/// List the implementor files of every source, relative to the sources. These are the files
+/// [`merge`] writes.
+pub(crate) fn files(sources: &[PathBuf]) -> Result<BTreeSet<PathBuf>> {
+    let mut files = BTreeSet::new();
+    for src in sources {
+        for dir in DIRS {
+            let root = src.join(dir);
+            if !root.is_dir() {
+                continue;
+            }
+            files.extend(
+                fsutil::walk_files(&root)?
+                    .into_iter()
+                    .filter(|rel| rel.extension().is_some_and(|ext| ext == "js"))
+                    .map(|rel| Path::new(dir).join(rel)),
+            );
+        }
+    }
+    Ok(files)
+}
+
+/// Merge the implementor files of every source into `dest`.
+///
+/// Each crate's implementations are taken from the same source its search index entry was taken
+/// from, and those of the crates left out of the merge are dropped. With the [`link_regex`] as
+/// `links`, the links to the docs.rs pages of the merged crates are made local.
+///
+/// [`link_regex`]: docs_rs::link_regex
+pub(crate) fn merge(
+    sources: &[PathBuf],
+    report: &MergeReport,
+    dest: &Path,
+    links: Option<&Regex>,
+) -> Result<()> {
+    let mut files = BTreeMap::<PathBuf, ImplFile>::new();
+    for src in sources {
+        for dir in DIRS {
+            let root = src.join(dir);
+            if !root.is_dir() {
+                continue;
+            }
+            for rel in fsutil::walk_files(&root)? {
+                if rel.extension().is_none_or(|ext| ext != "js") {
+                    continue;
+                }
+                let mut file = ImplFile::read(&root.join(&rel))?;
+                file.crates.retain(|name, _| report.takes(name, src));
+                match files.entry(Path::new(dir).join(rel)) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(file);
+                    }
+                    Entry::Occupied(mut entry) => entry.get_mut().crates.extend(file.crates),
+                }
+            }
+        }
+    }
+    for (rel, mut file) in files {
+        if let Some(links) = links {
+            for data in file.crates.values_mut() {
+                docs_rs::localize_impls(data, &report.crates, links);
+            }
+        }
+        file.write(&dest.join(rel))?;
+    }
+    Ok(())
+}
+
+/// Remove the implementations listed by `crates` from the implementor files of the site at
+/// `dest`. Files that no longer list any implementations are deleted.
+pub(crate) fn remove(dest: &Path, crates: &BTreeSet<String>) -> Result<()> {
+    for dir in DIRS {
+        let root = dest.join(dir);
+        if !root.is_dir() {
+            continue;
+        }
+        for rel in fsutil::walk_files(&root)? {
+            if rel.extension().is_none_or(|ext| ext != "js") {
+                continue;
+            }
+            let path = root.join(rel);
+            let mut file = ImplFile::read(&path)?;
+            let before = file.crates.len();
+            file.crates.retain(|name, _| !crates.contains(name));
+            if file.crates.is_empty() {
+                fs::remove_file(&path).map_err(MergeError::io(&path))?;
+            } else if file.crates.len() != before {
+                file.write(&path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `trait.impl/alpha/trait.Greet.js` as written by rustdoc 1.95.
+    const TRAIT_IMPL_1_95: &str = r#"(function() {
+    const implementors = Object.fromEntries([["beta",[["impl Greet for <a class=\"struct\" href=\"beta/struct.Other.html\" title=\"struct beta::Other\">Other</a>",0]]]]);
+    if (window.register_implementors) {
+        window.register_implementors(implementors);
+    } else {
+        window.pending_implementors = implementors;
+    }
+})()
+//{"start":59,"fragment_lengths":[122]}"#;
+
+    /// `trait.impl/core/clone/trait.Clone.js` as written by rustdoc 1.95 for two crates.
+    const TRAIT_CLONE_1_95: &str = r#"(function() {
+    const implementors = Object.fromEntries([["alpha",[["impl <a class=\"trait\" href=\"https://doc.rust-lang.org/1.95.0/core/clone/trait.Clone.html\" title=\"trait core::clone::Clone\">Clone</a> for <a class=\"struct\" href=\"alpha/struct.Thing.html\" title=\"struct alpha::Thing\">Thing</a>",0]]],["beta",[["impl <a class=\"trait\" href=\"https://doc.rust-lang.org/1.95.0/core/clone/trait.Clone.html\" title=\"trait core::clone::Clone\">Clone</a> for <a class=\"struct\" href=\"beta/struct.Other.html\" title=\"struct beta::Other\">Other</a>",0]]]]);
+    if (window.register_implementors) {
+        window.register_implementors(implementors);
+    } else {
+        window.pending_implementors = implementors;
+    }
+})()
+//{"start":59,"fragment_lengths":[253,251]}"#;
+
+    /// `trait.impl/core/fmt/trait.Debug.js` as written by rustdoc 1.90.
+    const TRAIT_IMPL_1_90: &str = r#"(function() {
+    var implementors = Object.fromEntries([["beta",[["impl <a class=\"trait\" href=\"https://doc.rust-lang.org/1.90.0/core/fmt/trait.Debug.html\" title=\"trait core::fmt::Debug\">Debug</a> for <a class=\"struct\" href=\"beta/struct.Other.html\" title=\"struct beta::Other\">Other</a>"]]]]);
+    if (window.register_implementors) {
+        window.register_implementors(implementors);
+    } else {
+        window.pending_implementors = implementors;
+    }
+})()
+//{"start":57,"fragment_lengths":[244]}"#;
+
+    /// `type.impl/ti/struct.S.js` as written by rustdoc 1.95.
+    const TYPE_IMPL_1_95: &str = r##"(function() {
+    var type_impls = Object.fromEntries([["ti",[["<details class=\"toggle implementors-toggle\" open><summary><section id=\"impl-S%3CT%3E\" class=\"impl\"><a class=\"src rightside\" href=\"src/ti/lib.rs.html#2\">Source</a><a href=\"#impl-S%3CT%3E\" class=\"anchor\">§</a><h3 class=\"code-header\">impl&lt;T&gt; <a class=\"struct\" href=\"ti/struct.S.html\" title=\"struct ti::S\">S</a>&lt;T&gt;</h3></section></summary><div class=\"impl-items\"><section id=\"method.f\" class=\"method\"><a class=\"src rightside\" href=\"src/ti/lib.rs.html#2\">Source</a><h4 class=\"code-header\">pub fn <a href=\"#method.f\" class=\"fn\">f</a>(&amp;self)</h4></section></div></details>",0,"ti::A"]]]]);
+    if (window.register_type_impls) {
+        window.register_type_impls(type_impls);
+    } else {
+        window.pending_type_impls = type_impls;
+    }
+})()
+//{"start":55,"fragment_lengths":[643]}"##;
+
+    /// `trait.impl/core/clone/trait.Clone.js` as written before rustdoc 1.83, without the
+    /// fragment comment.
+    const TRAIT_IMPL_FROM_ENTRIES: &str = "(function() {var implementors = Object.fromEntries([[\"gamma\",[[\"impl Clone for gamma::S\"]]]]);\nif (window.register_implementors) {window.register_implementors(implementors);} else {window.pending_implementors = implementors;}})()\n";
+
+    /// `implementors/core/clone/trait.Clone.js` as written before rustdoc 1.76, with an object
+    /// literal.
+    const IMPLEMENTORS_OBJECT: &str = "(function() {var implementors = {\n\"c\":[[\"impl Clone for c::S\"]]\n};if (window.register_implementors) {window.register_implementors(implementors);} else {window.pending_implementors = implementors;}})()";
+
+    /// Read `content` as an implementor file.
+    fn read(dir: &Path, content: &str) -> ImplFile {
+        let path = dir.join("in.js");
+        fs::write(&path, content).unwrap();
+        ImplFile::read(&path).unwrap()
+    }
+
+    /// Write `file` and return what was written.
+    fn write(dir: &Path, file: &ImplFile) -> String {
+        let path = dir.join("out.js");
+        file.write(&path).unwrap();
+        fs::read_to_string(path).unwrap()
+    }
+
+    /// Check that every fragment the comment at the end of `content` describes is the data of
+    /// one of `crates`, in order. Like rustdoc, every fragment but the first starts with the
+    /// comma that separates it from the one before.
+    fn check_fragments(content: &str, crates: &[&str]) {
+        let (_, comment) = content.rsplit_once("//").unwrap();
+        let comment = jzon::parse(comment).unwrap();
+        let mut start = comment["start"].as_usize().unwrap();
+        for (i, (length, crate_name)) in comment["fragment_lengths"]
+            .members()
+            .zip(crates)
+            .enumerate()
+        {
+            let end = start + length.as_usize().unwrap();
+            let fragment = &content[start..end];
+            let separator = if i == 0 { "" } else { "," };
+            assert!(
+                fragment.starts_with(&format!("{separator}[\"{crate_name}\"")),
+                "{fragment}"
+            );
+            assert!(fragment.ends_with("]]"), "{fragment}");
+            start = end;
+        }
+        assert_eq!(comment["fragment_lengths"].len(), crates.len());
+        assert_eq!(&content[start..start + 2], "])");
+    }
+
+    #[test]
+    fn round_trips_every_generation() {
+        let dir = tempfile::tempdir().unwrap();
+        for content in [
+            TRAIT_IMPL_1_95,
+            TRAIT_IMPL_1_90,
+            TYPE_IMPL_1_95,
+            TRAIT_IMPL_FROM_ENTRIES,
+        ] {
+            assert_eq!(write(dir.path(), &read(dir.path(), content)), content);
+        }
+    }
+
+    #[test]
+    fn reads_the_implementations_of_each_crate() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = read(dir.path(), TRAIT_IMPL_1_95);
+        assert!(file.from_entries && file.fragments);
+        assert_eq!(file.crates.keys().collect::<Vec<_>>(), ["beta"]);
+        assert_eq!(file.crates["beta"][0][1], 0);
+
+        let file = read(dir.path(), TYPE_IMPL_1_95);
+        assert_eq!(file.crates["ti"][0][2], "ti::A");
+
+        let file = read(dir.path(), IMPLEMENTORS_OBJECT);
+        assert!(!file.from_entries && !file.fragments);
+        assert_eq!(file.crates["c"][0][0], "impl Clone for c::S");
+    }
+
+    #[test]
+    fn writes_the_object_form_compactly() {
+        let dir = tempfile::tempdir().unwrap();
+        let written = write(dir.path(), &read(dir.path(), IMPLEMENTORS_OBJECT));
+        assert_eq!(
+            written,
+            "(function() {var implementors = {\"c\":[[\"impl Clone for c::S\"]]};if \
+             (window.register_implementors) {window.register_implementors(implementors);} else \
+             {window.pending_implementors = implementors;}})()"
+        );
+        assert_eq!(
+            read(dir.path(), &written).crates,
+            read(dir.path(), IMPLEMENTORS_OBJECT).crates
+        );
+    }
+
+    #[test]
+    fn describes_the_fragments_of_merged_crates() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut file = read(dir.path(), TRAIT_IMPL_1_95);
+        let alpha = TRAIT_IMPL_1_95.replace("beta", "alpha");
+        file.crates.extend(read(dir.path(), &alpha).crates);
+        let written = write(dir.path(), &file);
+        check_fragments(&written, &["alpha", "beta"]);
+        assert_eq!(read(dir.path(), &written).crates, file.crates);
+    }
+
+    #[test]
+    fn writes_the_fragments_of_merged_crates_like_rustdoc() {
+        let dir = tempfile::tempdir().unwrap();
+        check_fragments(TRAIT_CLONE_1_95, &["alpha", "beta"]);
+        let mut alpha = read(dir.path(), TRAIT_CLONE_1_95);
+        let mut beta = read(dir.path(), TRAIT_CLONE_1_95);
+        alpha.crates.remove("beta");
+        beta.crates.remove("alpha");
+        alpha.crates.extend(beta.crates);
+        assert_eq!(write(dir.path(), &alpha), TRAIT_CLONE_1_95);
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("in.js");
+        fs::write(&path, "var searchIndex = {};").unwrap();
+        assert!(ImplFile::read(&path).is_err());
+    }
+}
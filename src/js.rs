@@ -0,0 +1,126 @@
+//! Helpers for reading and writing the small JavaScript files rustdoc uses to ship its data.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use jzon::JsonValue;
+use regex::Regex;
+
+use crate::MergeError;
+
+/// Read a file containing a `JSON.parse('...')` call and parse its argument.
+pub(crate) fn read_json_parse_call(path: &Path) -> Result<JsonValue> {
+    let content = fs::read_to_string(path).map_err(MergeError::io(path))?;
+    let regex = Regex::new(r"(?s)JSON\.parse\('((?:[^'\\]|\\.)*)'\)")?;
+    let raw = regex
+        .captures(&content)
+        .ok_or_else(|| MergeError::invalid(path, "no JSON.parse('...') call found"))?;
+    Ok(jzon::parse(&unescape_string(&raw[1]))
+        .map_err(|err| MergeError::invalid(path, format!("invalid JSON: {err}")))?)
+}
+
+/// Escape a string so that it can be placed in a single-quoted JavaScript string literal.
+pub(crate) fn escape_string(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+/// Undo the escaping of a single-quoted JavaScript string literal.
+///
+/// rustdoc escapes backslashes and quotes, and older versions break long strings over several
+/// lines with a trailing backslash.
+pub(crate) fn unescape_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\n') | None => {}
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+        }
+    }
+    out
+}
+
+/// Join per-crate fragments with commas, the way rustdoc lays out its shared files.
+///
+/// Since rustdoc 1.83, shared files end with a `//{"start":..,"fragment_lengths":[..]}` comment
+/// that lets rustdoc find each crate's fragment when it documents another crate into the same
+/// directory. `start` is the byte offset at which the joined fragments will be written. The
+/// length of every fragment but the first counts the comma before it.
+pub(crate) fn join_fragments(start: usize, fragments: &[String]) -> (String, String) {
+    let lengths = fragments
+        .iter()
+        .enumerate()
+        .map(|(i, f)| (f.len() + usize::from(i > 0)).to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    (
+        fragments.join(","),
+        format!("//{{\"start\":{start},\"fragment_lengths\":[{lengths}]}}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unescape_string_undoes_escape_string() {
+        let s = r#"{"desc":"it's a \"quote\" and a \\ backslash"}"#;
+        assert_eq!(unescape_string(&escape_string(s)), s);
+    }
+
+    #[test]
+    fn unescape_string_handles_escapes() {
+        assert_eq!(unescape_string(r"a\'b\\c\nd\te"), "a'b\\c\nd\te");
+        assert_eq!(unescape_string(r"trailing\"), "trailing");
+    }
+
+    #[test]
+    fn unescape_string_joins_continued_lines() {
+        // the source index of rustdoc 1.76 breaks its data over several lines
+        let data = "[\\\n[\"gamma\",[\"\",[],[\"lib.rs\"]]]\\\n]";
+        assert_eq!(unescape_string(data), r#"[["gamma",["",[],["lib.rs"]]]]"#);
+    }
+
+    #[test]
+    fn join_fragments_matches_rustdoc() {
+        // `src-files.js` as written by rustdoc 1.95, which starts with `createSrcSidebar('[`
+        let fragments = [
+            r#"["alpha",["",[["sub",[],["mod.rs"]]],["lib.rs"]]]"#.to_owned(),
+            r#"["beta",["",[],["lib.rs"]]]"#.to_owned(),
+        ];
+        let (body, comment) = join_fragments(19, &fragments);
+        assert_eq!(
+            body,
+            r#"["alpha",["",[["sub",[],["mod.rs"]]],["lib.rs"]]],["beta",["",[],["lib.rs"]]]"#
+        );
+        assert_eq!(comment, r#"//{"start":19,"fragment_lengths":[49,28]}"#);
+    }
+
+    #[test]
+    fn join_fragments_counts_bytes() {
+        let fragments = ["\"§\"".to_owned()];
+        let (body, comment) = join_fragments(0, &fragments);
+        assert_eq!(body, "\"§\"");
+        assert_eq!(comment, r#"//{"start":0,"fragment_lengths":[4]}"#);
+    }
+
+    #[test]
+    fn join_fragments_of_nothing() {
+        assert_eq!(
+            join_fragments(5, &[]),
+            (
+                String::new(),
+                r#"//{"start":5,"fragment_lengths":[]}"#.to_owned()
+            )
+        );
+    }
+}
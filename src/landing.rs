@@ -0,0 +1,186 @@
+//! The landing page at the root of the merged site.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use regex::Regex;
+
+use crate::{fsutil, html, MergeError};
+
+/// The title of the landing page, unless another one is configured.
+pub const DEFAULT_TITLE: &str = "Crates";
+
+/// What comes right before the list of crates in the landing page.
+const LIST_START: &str = "<dl class=\"item-table\">\n";
+
+/// What comes right after the list of crates in the landing page.
+const LIST_END: &str = "\n</dl>";
+
+/// How the landing page looks, on top of the rustdoc theme it shares with the rest of the site.
+///
+/// Each is a URL, relative to the landing page or absolute. The pages rustdoc writes keep their
+/// own theme, which `--default-theme` and `--extend-css` in the rustdoc flags change instead.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Theme {
+    /// The image shown next to the title.
+    pub(crate) logo: Option<String>,
+
+    /// The icon browsers show for the page.
+    pub(crate) favicon: Option<String>,
+
+    /// Stylesheets loaded after rustdoc's.
+    pub(crate) stylesheets: Vec<String>,
+}
+
+/// What the landing page shows about a crate.
+struct CrateSummary {
+    name: String,
+    version: Option<String>,
+    description: Option<String>,
+}
+
+impl CrateSummary {
+    /// Read the summary of `name` from its index.html in the merged site.
+    fn read(dest: &Path, name: &str) -> Self {
+        let page = fs::read_to_string(dest.join(name).join("index.html")).unwrap_or_default();
+        let version = Regex::new(r#"class="version">(?:Version )?([^<]+)<"#)
+            .expect("valid regex")
+            .captures(&page)
+            .map(|captures| captures[1].trim().to_owned());
+        // rustdoc falls back to a generic description for crates without docs.
+        let description = html::meta_content(&page, "description")
+            .filter(|description| !description.starts_with("API documentation for the Rust `"));
+        Self {
+            name: name.to_owned(),
+            version,
+            description,
+        }
+    }
+
+    fn to_html(&self) -> String {
+        format!(
+            "<dt><a class=\"mod\" href=\"{name}/index.html\">{name}</a>{version}</dt><dd>{description}</dd>",
+            name = html::escape(&self.name),
+            version = self
+                .version
+                .as_deref()
+                .map(|version| format!(" <span class=\"version\">{}</span>", html::escape(version)))
+                .unwrap_or_default(),
+            description = self.description.as_deref().unwrap_or_default(),
+        )
+    }
+}
+
+/// Write a landing page listing `crates` to the index.html of the merged site at `dest`.
+pub(crate) fn write<'a>(
+    dest: &Path,
+    title: &str,
+    theme: &Theme,
+    crates: impl IntoIterator<Item = &'a String>,
+) -> Result<PathBuf> {
+    let favicon = theme
+        .favicon
+        .as_deref()
+        .map(|url| format!("<link rel=\"icon\" href=\"{}\">\n", html::escape(url)))
+        .unwrap_or_default();
+    let mut stylesheets = stylesheets(dest)?;
+    for url in &theme.stylesheets {
+        stylesheets.push_str(&format!(
+            "\n<link rel=\"stylesheet\" href=\"{}\">",
+            html::escape(url)
+        ));
+    }
+    let logo = theme
+        .logo
+        .as_deref()
+        .map(|url| {
+            format!(
+                "<img class=\"logo\" src=\"{}\" alt=\"\" style=\"height: 1.2em; \
+                 vertical-align: middle; margin-right: 0.4em\">",
+                html::escape(url)
+            )
+        })
+        .unwrap_or_default();
+    let path = dest.join("index.html");
+    fsutil::write_file(
+        &path,
+        format!(
+            include_str!("./templates/index.html"),
+            title = html::escape(title),
+            favicon = favicon,
+            stylesheets = stylesheets,
+            logo = logo,
+            crates = crate_list(dest, crates),
+        ),
+    )?;
+    Ok(path)
+}
+
+/// Rewrite the list of crates on the landing page of the site at `dest` to list `crates`,
+/// keeping the rest of the page, such as its title and theme.
+///
+/// Does nothing if the site's index.html is not a landing page written by doc-merge.
+pub(crate) fn refresh<'a>(dest: &Path, crates: impl IntoIterator<Item = &'a String>) -> Result<()> {
+    let path = dest.join("index.html");
+    let page = fs::read_to_string(&path).unwrap_or_default();
+    if html::meta_content(&page, "generator").as_deref() != Some("doc-merge") {
+        return Ok(());
+    }
+    let Some(start) = page.find(LIST_START).map(|i| i + LIST_START.len()) else {
+        return Ok(());
+    };
+    let Some(len) = page[start..].find(LIST_END) else {
+        return Ok(());
+    };
+    let list = crate_list(dest, crates);
+    fsutil::write_file(
+        &path,
+        format!("{}{list}{}", &page[..start], &page[start + len..]),
+    )
+}
+
+/// The entries of the list of `crates` on the landing page of the site at `dest`.
+fn crate_list<'a>(dest: &Path, crates: impl IntoIterator<Item = &'a String>) -> String {
+    crates
+        .into_iter()
+        .map(|name| CrateSummary::read(dest, name).to_html())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Link the rustdoc stylesheets of the merged site, so that the landing page matches the rest of
+/// the documentation.
+fn stylesheets(dest: &Path) -> Result<String> {
+    // rustdoc 1.67 and later keep their assets in static.files/, with a hash in their names.
+    let (dir, prefix) = if dest.join("static.files").is_dir() {
+        (dest.join("static.files"), "static.files/")
+    } else {
+        (dest.to_owned(), "")
+    };
+    // Sources built by several toolchains leave several versions of each stylesheet behind, so
+    // use the newest one. normalize.css has to come first.
+    let mut names = Vec::new();
+    for stem in ["normalize", "rustdoc"] {
+        let newest = fs::read_dir(&dir)
+            .map_err(MergeError::io(&dir))?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let modified = entry.metadata().and_then(|meta| meta.modified()).ok();
+                (name.starts_with(stem) && name.ends_with(".css")).then_some((modified, name))
+            })
+            .max();
+        names.extend(newest.map(|(_, name)| name));
+    }
+    Ok(names
+        .iter()
+        .map(|name| {
+            format!(
+                "<link rel=\"stylesheet\" href=\"{prefix}{}\">",
+                html::escape(name)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
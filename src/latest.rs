@@ -0,0 +1,98 @@
+//! The `latest/` alias of a site holding several versions of the documentation.
+//!
+//! Links into a versioned site go stale as soon as a new version is merged. The `latest/`
+//! directory always leads to the newest version, so that links to it never need updating.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::staging::Staging;
+use crate::{fsutil, html, MergeError};
+
+/// The name of the alias, relative to the site.
+pub(crate) const DIR: &str = "latest";
+
+/// How the `latest/` alias leads to the newest version.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Latest {
+    /// A symbolic link to the directory of the newest version.
+    #[default]
+    Symlink,
+
+    /// A page for every page of the newest version, redirecting to it. For web servers that do
+    /// not follow symbolic links.
+    Redirect,
+}
+
+named_options!(Latest, "kind of alias", {
+    Symlink => "symlink",
+    Redirect => "redirect",
+});
+
+impl Latest {
+    /// Point the alias in the site at `dest` to `version`.
+    ///
+    /// The alias is replaced in one step, so readers following it never find it missing or half
+    /// written.
+    pub(crate) fn update(self, dest: &Path, version: &str) -> Result<()> {
+        let alias = dest.join(DIR);
+        match self {
+            Self::Symlink => {
+                if fs::read_link(&alias).is_ok_and(|target| target == Path::new(version)) {
+                    return Ok(());
+                }
+                // renaming over a symbolic link replaces it atomically, but a directory of
+                // redirects from an earlier merge has to go first
+                if alias.is_dir() && !alias.is_symlink() {
+                    fs::remove_dir_all(&alias).map_err(MergeError::io(&alias))?;
+                }
+                let temp = dest.join(format!(".{DIR}.link"));
+                fsutil::prepare(&temp)?;
+                symlink_dir(Path::new(version), &temp).map_err(MergeError::io(&temp))?;
+                fs::rename(&temp, &alias).map_err(MergeError::io(&alias))?;
+            }
+            Self::Redirect => {
+                let staging = Staging::empty(&alias, dest)?;
+                if let Err(err) = write_redirects(&dest.join(version), version, staging.path()) {
+                    staging.discard();
+                    return Err(err);
+                }
+                if let Some(previous) = staging.commit()? {
+                    fs::remove_dir_all(&previous).map_err(MergeError::io(&previous))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Write a page into `dir` for every page of `version` at `src`, redirecting to it.
+fn write_redirects(src: &Path, version: &str, dir: &Path) -> Result<()> {
+    for rel in fsutil::walk_files(src)? {
+        if rel.extension().is_none_or(|ext| ext != "html") {
+            continue;
+        }
+        let up = "../".repeat(rel.components().count());
+        let url = PathBuf::from(format!("{up}{version}")).join(&rel);
+        fsutil::write_file(
+            &dir.join(&rel),
+            html::redirect_page(&url.to_string_lossy().replace('\\', "/")),
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink_dir(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink_dir(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_dir(target, link)
+}
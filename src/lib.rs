@@ -0,0 +1,88 @@
+//! This crate provides a primitive `doc-merge` command.
+//!
+//! It does one and exactly one thing: it lets you combine the `cargo doc` output from multiple
+//! crates into one location and adds an index. If you have multiple crates that you want to
+//! combine into a single documentation site, this crate might be what you need.
+//!
+//! While it's not a requirement, this crate is written with the expectation that you are usually
+//! running `cargo doc --no-deps`, because you're trying to document your own crates, and not their
+//! dependencies.
+//!
+//! ## Installation
+//!
+//! ```sh
+//! $ cargo install doc-merge
+//! ```
+//!
+//! ## Usage
+//!
+//! ```sh
+//! $ doc-merge --src /path/to/crate/target/doc/ --src /path/to/other/target/doc --dest /path/to/docs/
+//! ```
+//!
+//! The root of the merged site gets a landing page listing every crate, with its version and the
+//! summary from its documentation. Pass `--index-crate` to send readers to one crate instead.
+//!
+//! ## Library usage
+//!
+//! The merge engine is also available as a library, for tools that would rather not shell out to
+//! the binary:
+//!
+//! ```no_run
+//! use doc_merge::Merger;
+//!
+//! let report = Merger::new("./docs")
+//!     .source("/path/to/crate/target/doc")
+//!     .source("/path/to/other/target/doc")
+//!     .index_crate("my_crate")
+//!     .execute()?;
+//! for (name, source) in &report.crates {
+//!     println!("{name} <- {}", source.display());
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+#[macro_use]
+mod macros;
+
+mod check;
+mod config;
+mod conflict;
+mod doc_build;
+mod docs_rs;
+mod error;
+mod filter;
+mod fsutil;
+mod html;
+mod implementors;
+mod js;
+mod landing;
+mod latest;
+mod link;
+mod merger;
+mod prune;
+mod redirects;
+mod remove;
+mod rustdoc_version;
+pub mod search_index;
+mod src_files;
+mod staging;
+mod stringdex;
+mod unchanged;
+mod versions;
+mod workspace;
+
+pub use check::{check, BrokenLink, CheckReport, LinkProblem};
+pub use config::Config;
+pub use conflict::{Conflict, ConflictPolicy};
+pub use doc_build::{BuildFailure, DocBuild};
+pub use error::MergeError;
+pub use filter::Glob;
+pub use latest::Latest;
+pub use link::LinkMode;
+pub use merger::{MergeReport, Merger};
+pub use prune::Prune;
+pub use remove::remove;
+pub use rustdoc_version::VersionMismatch;
+pub use unchanged::Unchanged;
+pub use workspace::Workspace;
@@ -0,0 +1,174 @@
+//! Deduplication of identical files in the merged site.
+//!
+//! The versions of a site with several mostly hold the same files, such as the same shared
+//! assets, and the sources of one merge can hold identical files under different names. With a
+//! [`LinkMode`] other than `copy`, a copied file that is identical to the one at the same path in
+//! another version of the site, to the one it replaces, or to another file copied by the same
+//! merge, is linked to that file instead. Symbolic links are only made to files copied by the same
+//! merge, into the same version.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::Hasher;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::{fsutil, MergeError, Unchanged};
+
+/// How files identical to one already in the site are written to the merged site.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LinkMode {
+    /// Copy every file, even identical ones.
+    #[default]
+    Copy,
+
+    /// Hard link each file to its twin.
+    Hardlink,
+
+    /// Make each file a copy-on-write clone of its twin. Filesystems that cannot clone files get
+    /// plain copies instead.
+    Reflink,
+
+    /// Make each file a relative symbolic link to its twin, if its twin is another file copied by
+    /// the same merge. Files are never linked to files already in the site, which can be
+    /// replaced or pruned by a later merge.
+    Symlink,
+}
+
+named_options!(LinkMode, "link mode", {
+    Copy => "copy",
+    Hardlink => "hardlink",
+    Reflink => "reflink",
+    Symlink => "symlink",
+});
+
+impl LinkMode {
+    /// Write `twin` as a link to its original.
+    pub(crate) fn link(self, twin: &Twin) -> Result<()> {
+        let (original, path) = (&twin.original, &twin.path);
+        fsutil::prepare(path)?;
+        match self {
+            Self::Copy => fs::copy(original, path).map(|_| ()),
+            Self::Hardlink => fs::hard_link(original, path),
+            Self::Reflink => reflink_copy::reflink_or_copy(original, path).map(|_| ()),
+            Self::Symlink => symlink(&twin.target, path),
+        }
+        .map_err(MergeError::io(path))?;
+        Ok(())
+    }
+}
+
+/// Pairs of files, such as a file to copy and where to copy it to.
+pub(crate) type FilePairs = Vec<(PathBuf, PathBuf)>;
+
+/// A directory of the live site laid out like the destination, whose files the files copied into
+/// the destination can be linked to.
+#[derive(Debug, Clone)]
+pub(crate) struct LinkDir {
+    /// The directory.
+    pub(crate) path: PathBuf,
+
+    /// Where the directory is relative to the destination, once the destination is in place.
+    pub(crate) from_dest: PathBuf,
+}
+
+/// A file to link to an identical file, instead of copying it.
+#[derive(Debug)]
+pub(crate) struct Twin {
+    /// The identical file, in the site or copied by the merge.
+    pub(crate) original: PathBuf,
+
+    /// The original, relative to the directory of the twin once the destination is in place.
+    pub(crate) target: PathBuf,
+
+    /// Where the twin is written.
+    pub(crate) path: PathBuf,
+}
+
+/// Split `(from, to)` pairs of files to copy into `dest` into the pairs that have to be copied,
+/// and the twins of files at the same path below one of `dirs`, or of files copied before them.
+///
+/// Only files of the same length are read and compared, so the files that have no twin, such as
+/// those of a site's first version, are not read at all.
+pub(crate) fn dedup(
+    files: FilePairs,
+    dest: &Path,
+    dirs: &[LinkDir],
+) -> Result<(FilePairs, Vec<Twin>)> {
+    let mut lengths = BTreeMap::<u64, usize>::new();
+    let mut sized = Vec::with_capacity(files.len());
+    for (from, to) in files {
+        let len = fs::metadata(&from).map_err(MergeError::io(&from))?.len();
+        *lengths.entry(len).or_default() += 1;
+        sized.push((len, from, to));
+    }
+
+    // the files copied so far, told apart by their length and hash, and then compared byte by
+    // byte in case two different files share a hash
+    let mut copied = BTreeMap::<(u64, u64), Vec<(PathBuf, PathBuf)>>::new();
+    let mut copies = Vec::new();
+    let mut twins = Vec::new();
+    'files: for (len, from, to) in sized {
+        let rel = to.strip_prefix(dest).unwrap_or(&to);
+        let dir_of_rel = rel.parent().unwrap_or(Path::new(""));
+        for dir in dirs {
+            let original = dir.path.join(rel);
+            if original.is_file() && Unchanged::Content.is_unchanged(&from, &original)? {
+                twins.push(Twin {
+                    original,
+                    target: relative_path(dir_of_rel, &dir.from_dest.join(rel)),
+                    path: to,
+                });
+                continue 'files;
+            }
+        }
+        if lengths[&len] > 1 {
+            let contents = fs::read(&from).map_err(MergeError::io(&from))?;
+            let mut hasher = DefaultHasher::new();
+            hasher.write(&contents);
+            let originals = copied.entry((len, hasher.finish())).or_default();
+            for (original_from, original) in originals.iter() {
+                if Unchanged::Content.is_unchanged(&from, original_from)? {
+                    twins.push(Twin {
+                        original: original.clone(),
+                        target: relative_path(
+                            dir_of_rel,
+                            original.strip_prefix(dest).unwrap_or(original),
+                        ),
+                        path: to,
+                    });
+                    continue 'files;
+                }
+            }
+            originals.push((from.clone(), to.clone()));
+        }
+        copies.push((from, to));
+    }
+    Ok((copies, twins))
+}
+
+/// The path of `target` relative to the directory `dir`.
+pub(crate) fn relative_path(dir: &Path, target: &Path) -> PathBuf {
+    let dir = dir.components().collect::<Vec<_>>();
+    let target = target.components().collect::<Vec<_>>();
+    let common = dir.iter().zip(&target).take_while(|(a, b)| a == b).count();
+    dir[common..]
+        .iter()
+        .map(|_| Component::ParentDir)
+        .chain(target[common..].iter().copied())
+        .collect()
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(target, link)
+}
@@ -0,0 +1,45 @@
+//! Macros shared by the modules of the crate.
+
+/// Give a fieldless enum of options the names they go by on the command line and in the
+/// configuration file: an `ALL` constant listing every variant, and [`Display`] and [`FromStr`]
+/// implementations using the names.
+///
+/// The names must match the enum's `serde` names, so that the configuration file takes the same
+/// values as the command line.
+///
+/// [`Display`]: std::fmt::Display
+/// [`FromStr`]: std::str::FromStr
+macro_rules! named_options {
+    ($type:ident, $what:literal, { $($variant:ident => $name:literal),+ $(,)? }) => {
+        impl $type {
+            /// Every variant, in the order they are listed in help text.
+            pub const ALL: &'static [Self] = &[$(Self::$variant),+];
+        }
+
+        impl std::fmt::Display for $type {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(match self {
+                    $(Self::$variant => $name),+
+                })
+            }
+        }
+
+        impl std::str::FromStr for $type {
+            type Err = anyhow::Error;
+
+            fn from_str(s: &str) -> anyhow::Result<Self> {
+                Self::ALL
+                    .iter()
+                    .find(|option| option.to_string() == s)
+                    .copied()
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "unknown {} `{s}`, expected one of: {}",
+                            $what,
+                            [$($name),+].join(", ")
+                        )
+                    })
+            }
+        }
+    };
+}
@@ -1,40 +1,82 @@
-//! This crate provides a primitive `doc-merge` command.
+//! The `doc-merge` command-line interface.
 //!
-//! It does one and exactly one thing: it lets you combine the `cargo doc` output from multiple
-//! crates into one location and adds an index. If you have multiple crates that you want to
-//! combine into a single documentation site, this crate might be what you need.
-//!
-//! While it's not a requirement, this crate is written with the expectation that you are usually
-//! running `cargo doc --no-deps`, because you're trying to document your own crates, and not their
-//! dependencies.
-//!
-//! ## Installation
-//!
-//! ```sh
-//! $ cargo install doc-merge
-//! ```
-//!
-//! ## Usage
-//!
-//! ```sh
-//! $ doc-merge --src /path/to/crate/target/doc/ --src /path/to/other/target/doc --dest /path/to/docs/
-//! ```
+//! This is a thin wrapper around [`doc_merge::Merger`] and [`doc_merge::Config`]; see the library
+//! documentation for details.
 
-use std::collections::BTreeMap;
-use std::fs;
-use std::io::Write;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-use anyhow::Result;
-use clap::Parser;
-use fs_extra::dir::CopyOptions;
-use jzon::JsonValue;
-use regex::Regex;
+use anyhow::{bail, Result};
+use clap::{Args, Parser, Subcommand};
+use doc_merge::{
+    Config, ConflictPolicy, Latest, LinkMode, MergeError, MergeReport, Prune, Unchanged,
+    VersionMismatch,
+};
 
 /// Merge an individiual cargo doc site into a shared rustdoc site.
+///
+/// Settings can also be read from a `doc-merge.toml` file; flags given on the command line take
+/// precedence over the file.
 #[derive(Debug, Parser)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
 struct DocMerge {
+    #[command(flatten)]
+    merge: MergeArgs,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Add crates to an existing shared rustdoc site, or update the crates already in it.
+    ///
+    /// The crates already in the site are kept, unless one of the sources documents them again.
+    #[command(visible_alias = "update")]
+    Add(Box<MergeArgs>),
+
+    /// Remove crates from a shared rustdoc site.
+    Remove(RemoveArgs),
+
+    /// Check a shared rustdoc site for broken links.
+    ///
+    /// Every relative link in the site's pages and implementor lists is followed, and the ones
+    /// leading to a missing file or anchor are listed by crate. Exits with an error if there are
+    /// any.
+    Check(CheckArgs),
+}
+
+#[derive(Debug, Args)]
+struct CheckArgs {
+    /// The root of the shared rustdoc site [default: ./docs]
+    #[arg(long)]
+    dest: Option<PathBuf>,
+
+    /// The configuration file to read the destination from [default: ./doc-merge.toml, if it
+    /// exists]
+    #[arg(long)]
+    config: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct RemoveArgs {
+    /// The crates to remove.
+    #[arg(required = true)]
+    crates: Vec<String>,
+
+    /// The root of the shared rustdoc site [default: ./docs]
+    #[arg(long)]
+    dest: Option<PathBuf>,
+
+    /// The configuration file to read the destination from [default: ./doc-merge.toml, if it
+    /// exists]
+    #[arg(long)]
+    config: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct MergeArgs {
     /// The locations of documentations to merge together.
     ///
     /// The documentation is expected to already be built, usually with `cargo doc --no-deps` or
@@ -42,124 +84,524 @@ struct DocMerge {
     #[arg(long)]
     src: Vec<PathBuf>,
 
-    /// The root of the shared rustdoc site.
-    #[arg(long, default_value = "./docs")]
-    dest: PathBuf,
+    /// Cargo workspaces to merge the documentation of.
+    ///
+    /// The members of each workspace are found with `cargo metadata`, and their documentation is
+    /// taken from the workspace's target directory. Dependencies are left out.
+    #[arg(long)]
+    workspace: Vec<PathBuf>,
+
+    /// Only merge the workspace members matching one of these globs.
+    #[arg(long)]
+    members: Vec<String>,
+
+    /// Run `cargo doc --workspace --no-deps` in every workspace before merging.
+    #[arg(long, overrides_with = "no_build")]
+    build: bool,
 
-    /// The name of the crate that will have it's index.html be symlinked to the target directory.
-    /// If note passed, no index.html will be symlinked.
+    /// Do not run `cargo doc`, even if the configuration file says to.
+    #[arg(long)]
+    no_build: bool,
+
+    /// Features to enable when running `cargo doc`.
+    #[arg(long, value_delimiter = ',')]
+    features: Vec<String>,
+
+    /// The target triple the workspaces are documented for.
+    #[arg(long)]
+    target: Option<String>,
+
+    /// Extra flags for rustdoc when running `cargo doc`, instead of the `RUSTDOCFLAGS`
+    /// environment variable.
+    #[arg(long, allow_hyphen_values = true)]
+    rustdocflags: Option<String>,
+
+    /// Only merge the crates matching one of these globs, such as `my_*`.
+    #[arg(long)]
+    include: Vec<String>,
+
+    /// Leave out the crates matching any of these globs, such as dependencies documented by
+    /// mistake.
+    #[arg(long)]
+    exclude: Vec<String>,
+
+    /// The root of the shared rustdoc site [default: ./docs]
+    #[arg(long)]
+    dest: Option<PathBuf>,
+
+    /// Merge into the directory of this version of the documentation, such as `1.2`, within the
+    /// site. The versions are listed in `versions.json`, and every page gets a menu for switching
+    /// between them.
+    #[arg(long)]
+    doc_version: Option<String>,
+
+    /// Keep a `latest` directory pointing at the newest version in the site: a symbolic link, or
+    /// with `--latest=redirect`, a page redirecting to every page of the newest version.
+    #[arg(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "symlink"
+    )]
+    latest: Option<Latest>,
+
+    /// Redirect from the pages of a crate or module that moved to its new pages, as
+    /// `OLD=NEW` paths such as `old_crate=new_crate` or `my_crate::old=my_crate::new`.
+    #[arg(long, value_name = "OLD=NEW", value_parser = parse_redirect)]
+    redirect: Vec<(String, String)>,
+
+    /// Redirect from the pages of the crates that were renamed since the last merge, found by
+    /// comparing their items with those of the crates new to the site.
+    #[arg(long, overrides_with = "no_detect_moves")]
+    detect_moves: bool,
+
+    /// Do not look for renamed crates, even if the configuration file says to.
+    #[arg(long)]
+    no_detect_moves: bool,
+
+    /// Rewrite links to the docs.rs pages of the merged crates, such as those rustdoc writes for
+    /// dependencies, into relative links to the merged site's own pages.
+    #[arg(long, overrides_with = "no_local_links")]
+    local_links: bool,
+
+    /// Leave links to docs.rs alone, even if the configuration file says to rewrite them.
+    #[arg(long)]
+    no_local_links: bool,
+
+    /// The name of the crate that the index.html at the root of the site sends readers to.
+    /// If not passed, the index.html lists every crate instead.
     #[arg(long)]
     index_crate: Option<String>,
-}
 
-macro_rules! fatal {
-  ($($arg:tt)*) => {{
-    eprintln!("Fatal: {}", format!($($arg)*));
-    std::process::exit(1);
-  }}
+    /// The title of the landing page that lists every crate [default: Crates]
+    #[arg(long, conflicts_with = "index_crate")]
+    title: Option<String>,
+
+    /// An image to show next to the title of the landing page, as a URL relative to the page.
+    #[arg(long, conflicts_with = "index_crate")]
+    logo: Option<String>,
+
+    /// The icon of the landing page, as a URL relative to the page.
+    #[arg(long, conflicts_with = "index_crate")]
+    favicon: Option<String>,
+
+    /// Stylesheets for the landing page to load after rustdoc's, as URLs relative to the page.
+    #[arg(long, conflicts_with = "index_crate")]
+    stylesheet: Vec<String>,
+
+    /// What to do when more than one source documents the same crate: error, first-wins,
+    /// last-wins or prefer-newest (the most recently built documentation) [default: last-wins]
+    #[arg(long)]
+    on_conflict: Option<ConflictPolicy>,
+
+    /// What to do when the sources were documented by different versions of rustdoc: warn,
+    /// error, or rewrite the pages of the older versions to use the assets of the newest
+    /// [default: warn]
+    #[arg(long)]
+    on_version_mismatch: Option<VersionMismatch>,
+
+    /// Delete the files in the destination that the merge did not produce, such as the pages of
+    /// renamed modules or removed crates. Pass `--prune=dry-run` to only list them.
+    #[arg(
+        long,
+        visible_alias = "clean",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "delete"
+    )]
+    prune: Option<Prune>,
+
+    /// How files identical to one already in the site, such as in another version, or to another
+    /// copied file are written: copy, or hardlink, reflink or symlink them to it [default: copy]
+    #[arg(long)]
+    link_mode: Option<LinkMode>,
+
+    /// Leave the files the merge would not change alone, so that they keep their modification
+    /// times: compare them by size and modification time (mtime), or by contents (content)
+    #[arg(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "mtime"
+    )]
+    skip_unchanged: Option<Unchanged>,
+
+    /// How many files to copy at the same time [default: the number of CPUs]
+    #[arg(long, short)]
+    jobs: Option<usize>,
+
+    /// Print what the merge would copy and write, without touching the destination or running
+    /// `cargo doc`.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// The configuration file to read [default: ./doc-merge.toml, if it exists]
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Print which source each crate was taken from.
+    #[arg(long, short)]
+    verbose: bool,
 }
 
 impl DocMerge {
     fn execute(self) -> Result<()> {
-        // Sanity check: Does the source directory exist?
-        if self.src.len() < 2 {
-            fatal!("At least two documentation paths must be passed for merging");
+        match self.command {
+            None => self.merge.execute(false),
+            Some(Command::Add(args)) => args.execute(true),
+            Some(Command::Remove(args)) => args.execute(),
+            Some(Command::Check(args)) => args.execute(),
         }
-        // create destination if it doesnt exist
-        fs::create_dir_all(&self.dest)?;
+    }
+}
 
-        // Copy the each subdirectory in the source to the destination (but not the files).
-        let opts = CopyOptions {
-            overwrite: true,
-            ..Default::default()
+/// Read the configuration file at `path`, or the default one if it exists.
+fn load_config(path: Option<&Path>) -> Result<Config> {
+    match path {
+        Some(path) => Config::load(path),
+        None if Path::new(Config::DEFAULT_PATH).is_file() => {
+            Config::load(Path::new(Config::DEFAULT_PATH))
+        }
+        None => Ok(Config::default()),
+    }
+}
+
+impl RemoveArgs {
+    fn execute(self) -> Result<()> {
+        let dest = match self.dest {
+            Some(dest) => dest,
+            None => load_config(self.config.as_deref())?
+                .dest
+                .unwrap_or_else(|| Config::DEFAULT_DEST.into()),
         };
-        for src in &self.src {
-            for entry in src.read_dir()? {
-                let entry = entry?;
-                if entry.path().is_dir() {
-                    fs_extra::copy_items(&[entry.path()], &self.dest, &opts)?;
-                }
-                if entry
-                    .file_name()
-                    .to_str()
-                    .expect("Invalid filename")
-                    .ends_with(".html")
-                {
-                    fs::copy(entry.path(), &self.dest.join(entry.file_name()))?;
-                }
+        doc_merge::remove(&dest, self.crates)
+    }
+}
+
+impl CheckArgs {
+    fn execute(self) -> Result<()> {
+        let dest = match self.dest {
+            Some(dest) => dest,
+            None => load_config(self.config.as_deref())?
+                .dest
+                .unwrap_or_else(|| Config::DEFAULT_DEST.into()),
+        };
+        let report = doc_merge::check(&dest)?;
+        for (crate_name, links) in &report.broken {
+            match crate_name.as_str() {
+                "" => println!("Shared pages:"),
+                crate_name => println!("{crate_name}:"),
+            }
+            for link in links {
+                println!("  {link}");
             }
         }
+        let broken = report.broken_count();
+        if broken > 0 {
+            bail!(
+                "Found {broken} broken links in {} pages of {}",
+                report.pages,
+                dest.display()
+            );
+        }
+        println!(
+            "Checked {} links in {} pages; none are broken",
+            report.links, report.pages
+        );
+        Ok(())
+    }
+}
 
-        // parse all search-index.js files for crates
-        let search_index_regex = Regex::new(r"JSON\.parse\('(.*)'\)")?;
-        let mut crates = BTreeMap::<String, JsonValue>::new();
-        for docs_path in &self.src {
-            let content = fs::read_to_string(docs_path.join("search-index.js"))?;
-            let search_index_raw = search_index_regex
-                .captures(&content)
-                .expect("search-index.js must have searchIndex");
-            let search_index = jzon::parse(&search_index_raw[1])?;
-            for item in search_index
-                .as_array()
-                .expect("searchIndex json must be array")
-            {
-                let crate_name = item[0].as_str().unwrap();
-                let crate_data = item[1].clone();
-                crates.insert(crate_name.to_owned(), crate_data);
+impl MergeArgs {
+    fn execute(self, incremental: bool) -> Result<()> {
+        let (dry_run, verbose) = (self.dry_run, self.verbose);
+        let config = self.into_config()?;
+        let prune = config.prune;
+        let skip_unchanged = config.skip_unchanged.is_some();
+        let index_page = match (&config.index_crate, &config.title) {
+            (Some(index_crate), _) => format!("a redirect to {index_crate}/index.html"),
+            (None, title) => format!(
+                "a landing page titled \"{}\"",
+                title.as_deref().unwrap_or(Config::DEFAULT_TITLE)
+            ),
+        };
+        let merger = config
+            .into_merger()?
+            .incremental(incremental)
+            .dry_run(dry_run);
+        let report = merger.execute()?;
+
+        for conflict in &report.conflicts {
+            eprintln!("Warning: {conflict}");
+        }
+        let versions = report.rustdoc_versions.values().collect::<BTreeSet<_>>();
+        if versions.len() > 1 {
+            eprintln!("Warning: the sources were documented by different versions of rustdoc:");
+            for (src, version) in &report.rustdoc_versions {
+                eprintln!("  {}: {version}", src.display());
             }
         }
-
-        // Write the crates.js file.
-        write!(
-            fs::OpenOptions::new()
-                .write(true)
-                .create(true)
-                .truncate(true)
-                .open(self.dest.as_path().join("crates.js"))?,
-            "window.ALL_CRATES = [{}];",
-            crates
-                .keys()
-                .map(|k| format!("\"{}\"", k))
-                .collect::<Vec<String>>()
-                .join(",")
-                .as_str()
-        )?;
-
-        // write search-index.js
-        let search_index_items = crates
-            .iter()
-            .map(|(name, data)| jzon::array![name.as_str(), data.to_owned()]);
-        let search_index_json = JsonValue::Array(search_index_items.collect());
-        write!(
-            fs::OpenOptions::new()
-                .write(true)
-                .create(true)
-                .truncate(true)
-                .open(self.dest.as_path().join("search-index.js"))?,
-            include_str!("./templates/search-index.js"),
-            searchIndexJson = search_index_json,
-        )?;
-
-        if let Some(index_crate) = self.index_crate.as_deref() {
-            let index_path = self.dest.as_path().join("index.html");
-            if fs::exists(&index_path)? {
-                fs::remove_file(&index_path)?;
+        if dry_run {
+            print_plan(&report, &index_page);
+        }
+        for rel in &report.stale {
+            let path = merger.dest().join(rel);
+            match prune {
+                Some(Prune::DryRun) => println!("Would remove {}", path.display()),
+                _ if dry_run => println!("Would remove {}", path.display()),
+                _ => println!("Removed {}", path.display()),
+            }
+        }
+        if skip_unchanged && !dry_run {
+            println!(
+                "Wrote {} files, skipped {} unchanged files",
+                report.files_written, report.files_skipped
+            );
+        }
+        if verbose {
+            for (crate_name, src) in &report.crates {
+                println!("{crate_name}: {}", src.display());
+            }
+            if !dry_run {
+                for (from, to) in &report.redirects {
+                    println!("Redirected the pages of {from} to {to}");
+                }
             }
-            #[cfg(unix)]
-            let symlink = std::os::unix::fs::symlink;
-            #[cfg(windows)]
-            let symlink = std::os::windows::fs::symlink_file;
-            symlink(
-                self.dest.as_path().join(index_crate).join("index.html"),
-                &index_path,
-            )?;
         }
-
         Ok(())
     }
+
+    /// The settings of the merge: those of the configuration file, overridden by the flags given
+    /// on the command line.
+    fn into_config(self) -> Result<Config> {
+        let mut config = load_config(self.config.as_deref())?;
+        if !self.src.is_empty() {
+            config.sources = self.src;
+        }
+        if !self.workspace.is_empty() {
+            config.workspaces = self.workspace;
+        }
+        if !self.members.is_empty() {
+            config.members = self.members;
+        }
+        if self.build {
+            config.build = true;
+        } else if self.no_build {
+            // the configuration's settings for `cargo doc` go unused along with it
+            config.build = false;
+            config.features.clear();
+            config.rustdocflags = None;
+        }
+        if !self.features.is_empty() {
+            config.features = self.features;
+        }
+        if !self.include.is_empty() {
+            config.include = self.include;
+        }
+        if !self.exclude.is_empty() {
+            config.exclude = self.exclude;
+        }
+        if self.target.is_some() {
+            config.target = self.target;
+        }
+        if self.rustdocflags.is_some() {
+            config.rustdocflags = self.rustdocflags;
+        }
+        if self.dest.is_some() {
+            config.dest = self.dest;
+        }
+        if self.doc_version.is_some() {
+            config.doc_version = self.doc_version;
+        }
+        if self.latest.is_some() {
+            config.latest = self.latest;
+        }
+        config.redirects.extend(self.redirect);
+        if self.detect_moves {
+            config.detect_moves = true;
+        } else if self.no_detect_moves {
+            config.detect_moves = false;
+        }
+        if self.local_links {
+            config.local_links = true;
+        } else if self.no_local_links {
+            config.local_links = false;
+        }
+        if self.index_crate.is_some() {
+            config.index_crate = self.index_crate;
+        }
+        if self.title.is_some() {
+            config.title = self.title;
+        }
+        if self.logo.is_some() {
+            config.logo = self.logo;
+        }
+        if self.favicon.is_some() {
+            config.favicon = self.favicon;
+        }
+        if !self.stylesheet.is_empty() {
+            config.stylesheets = self.stylesheet;
+        }
+        if self.on_conflict.is_some() {
+            config.on_conflict = self.on_conflict;
+        }
+        if self.on_version_mismatch.is_some() {
+            config.on_version_mismatch = self.on_version_mismatch;
+        }
+        if self.prune.is_some() {
+            config.prune = self.prune;
+        }
+        if self.skip_unchanged.is_some() {
+            config.skip_unchanged = self.skip_unchanged;
+        }
+        if self.link_mode.is_some() {
+            config.link_mode = self.link_mode;
+        }
+        if self.jobs.is_some() {
+            config.jobs = self.jobs;
+        }
+        Ok(config)
+    }
+}
+
+/// Parse a `--redirect` value.
+fn parse_redirect(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .map(|(from, to)| (from.to_owned(), to.to_owned()))
+        .ok_or_else(|| format!("expected OLD=NEW, found `{value}`"))
+}
+
+/// Print what a dry run of the merge would do.
+fn print_plan(report: &MergeReport, index_page: &str) {
+    let mut copies = BTreeMap::<&Path, Vec<&Path>>::new();
+    for (src, rel) in &report.copies {
+        copies.entry(src).or_default().push(rel);
+    }
+    for (src, rels) in copies {
+        println!("Would copy from {}:", src.display());
+        for rel in rels {
+            let slash = if src.join(rel).is_dir() { "/" } else { "" };
+            println!("  {}{slash}", rel.display());
+        }
+    }
+    println!("Would list these crates in crates.js and the search index:");
+    for (crate_name, src) in &report.crates {
+        if report.updated.contains(crate_name) {
+            println!(
+                "  {crate_name} (from {}, replacing the one in the site)",
+                src.display()
+            );
+        } else {
+            println!("  {crate_name} (from {})", src.display());
+        }
+    }
+    if !report.excluded.is_empty() {
+        println!(
+            "Would leave out: {}",
+            report
+                .excluded
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    if let Some(index) = &report.index {
+        println!("Would write {} as {index_page}", index.display());
+    }
+    if !report.versions.is_empty() {
+        println!(
+            "Would list these versions in versions.json: {}",
+            report.versions.join(", ")
+        );
+    }
+    if let Some(latest) = &report.latest {
+        println!("Would point latest/ at {latest}");
+    }
+    for (from, to) in &report.redirects {
+        println!("Would redirect the pages of {from} to {to}");
+    }
 }
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
     let doc_merge = DocMerge::parse();
-    doc_merge.execute()
+    match doc_merge.execute() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err:?}");
+            ExitCode::from(
+                err.downcast_ref::<MergeError>()
+                    .map_or(1, MergeError::exit_code),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    /// The settings of `doc-merge` run with `args`, and a configuration file holding `content`.
+    fn settings(content: &str, args: &[&str]) -> Config {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(Config::DEFAULT_PATH);
+        fs::write(&path, content).unwrap();
+        let path = path.to_str().unwrap();
+        let doc_merge =
+            DocMerge::try_parse_from(["doc-merge", "--config", path].iter().chain(args).copied())
+                .unwrap();
+        doc_merge.merge.into_config().unwrap()
+    }
+
+    #[test]
+    fn flags_take_precedence_over_the_configuration_file() {
+        let file = r#"
+            sources = ["a", "b"]
+            exclude = ["serde*"]
+            title = "From the file"
+            on_conflict = "error"
+            link_mode = "hardlink"
+        "#;
+        let config = settings(
+            file,
+            &[
+                "--src=/c",
+                "--title=From the flags",
+                "--on-conflict=first-wins",
+                "--dest=/out",
+            ],
+        );
+        assert_eq!(config.sources, [PathBuf::from("/c")]);
+        assert_eq!(config.title.as_deref(), Some("From the flags"));
+        assert_eq!(config.on_conflict, Some(ConflictPolicy::FirstWins));
+        assert_eq!(config.dest, Some(PathBuf::from("/out")));
+        // the settings no flag was given for are kept
+        assert_eq!(config.exclude, ["serde*"]);
+        assert_eq!(config.link_mode, Some(LinkMode::Hardlink));
+    }
+
+    #[test]
+    fn no_flags_turn_off_the_configuration_file() {
+        let file = r#"
+            workspaces = ["server"]
+            build = true
+            features = ["full"]
+            rustdocflags = "--cfg docsrs"
+            detect_moves = true
+            local_links = true
+        "#;
+        let config = settings(
+            file,
+            &["--no-build", "--no-detect-moves", "--no-local-links"],
+        );
+        assert!(!config.build && !config.detect_moves && !config.local_links);
+        assert!(config.features.is_empty());
+        assert_eq!(config.rustdocflags, None);
+
+        let config = settings("", &["--detect-moves", "--local-links"]);
+        assert!(config.detect_moves && config.local_links);
+    }
 }
@@ -0,0 +1,1040 @@
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use anyhow::{bail, Result};
+use jzon::JsonValue;
+
+use crate::conflict::{Conflict, ConflictPolicy};
+use crate::doc_build::DocBuild;
+use crate::filter::{CrateFilter, Glob};
+use crate::latest::Latest;
+use crate::link::{self, LinkDir, LinkMode};
+use crate::prune::{self, Prune};
+use crate::redirects;
+use crate::rustdoc_version::{self, AssetRewrite, VersionMismatch};
+use crate::search_index::{self, SearchIndex, SearchIndexFormat};
+use crate::staging::Staging;
+use crate::unchanged::Unchanged;
+use crate::versions;
+use crate::workspace::Workspace;
+use crate::{docs_rs, fsutil, html, implementors, js, landing, src_files, stringdex, MergeError};
+
+/// Directories holding one subdirectory per crate, such as the `src/` tree of the source browser.
+pub(crate) const PER_CRATE_DIRS: &[&str] = &["src", "search.desc"];
+
+/// Merges the rustdoc output of several crates into one shared rustdoc site.
+///
+/// A `Merger` is configured with the builder methods below and run with [`Merger::execute`].
+#[derive(Debug, Clone)]
+pub struct Merger {
+    sources: Vec<PathBuf>,
+    dest: PathBuf,
+    index_crate: Option<String>,
+    title: Option<String>,
+    theme: landing::Theme,
+    on_conflict: ConflictPolicy,
+    filter: CrateFilter,
+    workspaces: Vec<Workspace>,
+    build: Option<DocBuild>,
+    incremental: bool,
+    prune: Option<Prune>,
+    dry_run: bool,
+    jobs: usize,
+    link_mode: LinkMode,
+    link_dirs: Vec<LinkDir>,
+    skip_unchanged: Option<Unchanged>,
+    on_version_mismatch: VersionMismatch,
+    version: Option<String>,
+    latest: Option<Latest>,
+    redirects: BTreeMap<String, String>,
+    detect_moves: bool,
+    local_links: bool,
+}
+
+/// A summary of what a merge did.
+#[derive(Debug, Clone, Default)]
+pub struct MergeReport {
+    /// The crates in the merged site, and the source directory each one was taken from.
+    pub crates: BTreeMap<String, PathBuf>,
+
+    /// The crates that were already in the destination and have been replaced, when adding to an
+    /// existing site.
+    pub updated: BTreeSet<String>,
+
+    /// The crates that were documented by more than one source, and how each was resolved.
+    pub conflicts: Vec<Conflict>,
+
+    /// The version of rustdoc each source (and, when adding to a site, the destination) was
+    /// documented with, where it is known.
+    pub rustdoc_versions: BTreeMap<PathBuf, String>,
+
+    /// The crates that were left out by the include and exclude patterns, or because they are not
+    /// members of a workspace. When adding to a site, this includes the crates of the site that
+    /// the patterns leave out, which are removed from it.
+    pub excluded: BTreeSet<String>,
+
+    /// The directories and files copied into the destination, as a source directory and a path
+    /// relative to it. Each path is copied to the same place relative to the destination.
+    pub copies: Vec<(PathBuf, PathBuf)>,
+
+    /// The number of bytes copied into the destination.
+    pub bytes_copied: u64,
+
+    /// The number of files written to the destination, including the generated ones.
+    pub files_written: usize,
+
+    /// The number of files that were left alone because they were unchanged.
+    pub files_skipped: usize,
+
+    /// The number of files that were linked to an identical file already in the site, or copied
+    /// by the merge, instead of being copied.
+    pub files_linked: usize,
+
+    /// The index.html written to the root of the site, or that would be in a dry run.
+    pub index: Option<PathBuf>,
+
+    /// Where the site that was in the destination before the merge has been moved to, if there
+    /// was one.
+    pub previous: Option<PathBuf>,
+
+    /// The files in the destination that the merge did not produce, relative to the destination.
+    /// They have been deleted, unless pruning or the merge was a dry run.
+    pub stale: Vec<PathBuf>,
+
+    /// Every version in the site after the merge, newest first, when merging one of several
+    /// versions.
+    pub versions: Vec<String>,
+
+    /// The version the `latest` alias points at after the merge, if the merge keeps one.
+    pub latest: Option<String>,
+
+    /// The crate and module paths redirected to new ones, including the renamed crates that were
+    /// detected.
+    pub redirects: BTreeMap<String, String>,
+}
+
+impl MergeReport {
+    /// Whether the documentation of `crate_name` in `src` belongs in the merged site: the crate
+    /// is taken from `src`, or it is not a crate the merge knows about at all.
+    pub(crate) fn takes(&self, crate_name: &str, src: &Path) -> bool {
+        match self.crates.get(crate_name) {
+            Some(owner) => owner == src,
+            None => !self.excluded.contains(crate_name),
+        }
+    }
+}
+
+impl Merger {
+    /// Create a merger that writes the shared site to `dest`.
+    pub fn new(dest: impl Into<PathBuf>) -> Self {
+        Self {
+            sources: Vec::new(),
+            dest: dest.into(),
+            index_crate: None,
+            title: None,
+            theme: landing::Theme::default(),
+            on_conflict: ConflictPolicy::default(),
+            filter: CrateFilter::default(),
+            workspaces: Vec::new(),
+            build: None,
+            incremental: false,
+            prune: None,
+            dry_run: false,
+            jobs: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            link_mode: LinkMode::default(),
+            link_dirs: Vec::new(),
+            skip_unchanged: None,
+            on_version_mismatch: VersionMismatch::default(),
+            version: None,
+            latest: None,
+            redirects: BTreeMap::new(),
+            detect_moves: false,
+            local_links: false,
+        }
+    }
+
+    /// Add a documentation directory (usually `target/doc`) to merge.
+    pub fn source(mut self, path: impl Into<PathBuf>) -> Self {
+        self.sources.push(path.into());
+        self
+    }
+
+    /// Add several documentation directories to merge.
+    pub fn sources<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.sources.extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Add the documentation of a Cargo workspace's members to merge.
+    ///
+    /// Only the workspace's own crates are taken from its target directory, so dependencies that
+    /// were documented alongside them are left out.
+    pub fn workspace(mut self, workspace: Workspace) -> Self {
+        self.workspaces.push(workspace);
+        self
+    }
+
+    /// Run `cargo doc` for every workspace before merging.
+    pub fn build(mut self, build: DocBuild) -> Self {
+        self.build = Some(build);
+        self
+    }
+
+    /// Send readers of the root index.html straight on to the given crate's documentation,
+    /// instead of showing the list of crates.
+    pub fn index_crate(mut self, name: impl Into<String>) -> Self {
+        self.index_crate = Some(name.into());
+        self
+    }
+
+    /// Set the title of the landing page that lists every crate.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Show an image next to the title of the landing page. `url` is relative to the page, or
+    /// absolute.
+    pub fn logo(mut self, url: impl Into<String>) -> Self {
+        self.theme.logo = Some(url.into());
+        self
+    }
+
+    /// Set the icon browsers show for the landing page. `url` is relative to the page, or
+    /// absolute.
+    pub fn favicon(mut self, url: impl Into<String>) -> Self {
+        self.theme.favicon = Some(url.into());
+        self
+    }
+
+    /// Add a stylesheet for the landing page to load after rustdoc's, such as one overriding its
+    /// colors. `url` is relative to the page, or absolute.
+    ///
+    /// The pages rustdoc writes are left alone; pass `--extend-css` to rustdoc to style them.
+    pub fn stylesheet(mut self, url: impl Into<String>) -> Self {
+        self.theme.stylesheets.push(url.into());
+        self
+    }
+
+    /// Set what to do when more than one source documents the same crate.
+    ///
+    /// Defaults to [`ConflictPolicy::LastWins`].
+    pub fn on_conflict(mut self, policy: ConflictPolicy) -> Self {
+        self.on_conflict = policy;
+        self
+    }
+
+    /// Only merge crates whose name matches `glob` (or another include pattern).
+    pub fn include(mut self, glob: Glob) -> Self {
+        self.filter.include(glob);
+        self
+    }
+
+    /// Leave out crates whose name matches `glob`.
+    pub fn exclude(mut self, glob: Glob) -> Self {
+        self.filter.exclude(glob);
+        self
+    }
+
+    /// Set what to do when the sources were documented by different versions of rustdoc.
+    ///
+    /// Defaults to [`VersionMismatch::Warn`], which merges them anyway; the versions are listed
+    /// in [`MergeReport::rustdoc_versions`].
+    pub fn on_version_mismatch(mut self, policy: VersionMismatch) -> Self {
+        self.on_version_mismatch = policy;
+        self
+    }
+
+    /// Merge into the `version` directory of the destination, as one of several versions of the
+    /// documentation kept side by side.
+    ///
+    /// The version is added to the `versions.json` list at the root of the destination, and every
+    /// page gets a menu for switching to the same page in another version.
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Keep a `latest` alias in the destination pointing at the newest of its versions, when
+    /// merging one of several versions (see [`Merger::version`]).
+    pub fn latest(mut self, latest: Latest) -> Self {
+        self.latest = Some(latest);
+        self
+    }
+
+    /// Send readers of the pages of the crate or module at the path `from`, such as
+    /// `old_crate::module`, on to the same pages of the crate or module at the path `to`.
+    ///
+    /// A redirect page is written at the old location of every page below `to`, unless the merge
+    /// writes a page of its own there.
+    pub fn redirect(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.redirects.insert(from.into(), to.into());
+        self
+    }
+
+    /// Look for crates that were renamed since the last merge into the destination, by comparing
+    /// the items of the crates that are no longer in the site with those of the crates new to it,
+    /// and redirect from their old pages to the new ones.
+    pub fn detect_moves(mut self, detect_moves: bool) -> Self {
+        self.detect_moves = detect_moves;
+        self
+    }
+
+    /// Rewrite the links to docs.rs pages of the crates in the merged site, which rustdoc writes
+    /// for crates documented on their own, into relative links to the site's own pages.
+    pub fn local_links(mut self, local_links: bool) -> Self {
+        self.local_links = local_links;
+        self
+    }
+
+    /// Add the sources to the site already in the destination, instead of replacing it.
+    ///
+    /// The crates already in the site are kept, unless a source documents them again, in which
+    /// case their documentation is replaced. A single source is enough in this mode.
+    pub fn incremental(mut self, incremental: bool) -> Self {
+        self.incremental = incremental;
+        self
+    }
+
+    /// Look for files in the destination that the merge did not produce, such as the pages of
+    /// renamed modules or removed crates, and list or delete them.
+    ///
+    /// When adding to an existing site, the crates that stay in it are kept along with the
+    /// shared files their pages may use.
+    pub fn prune(mut self, prune: Prune) -> Self {
+        self.prune = Some(prune);
+        self
+    }
+
+    /// Work out what the merge would do without touching the destination.
+    ///
+    /// The returned report lists the crates, conflicts, copies and stale files as they would be,
+    /// but nothing is written, and `cargo doc` is not run for the workspaces.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Set how many files are copied at the same time.
+    ///
+    /// Defaults to the number of CPUs.
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs.max(1);
+        self
+    }
+
+    /// Set how files identical to one already in the site or to another file of the merge, such
+    /// as the assets in `static.files/` that every version of a site shares, are written to the
+    /// merged site.
+    ///
+    /// Defaults to [`LinkMode::Copy`], which writes every file on its own.
+    pub fn link_mode(mut self, mode: LinkMode) -> Self {
+        self.link_mode = mode;
+        self
+    }
+
+    /// Leave the files in the destination that the merge would not change alone, so that they
+    /// keep their modification times.
+    ///
+    /// Copied files are compared as set by `unchanged`; generated files such as `crates.js` and
+    /// the search index are always compared by their contents.
+    pub fn skip_unchanged(mut self, unchanged: Unchanged) -> Self {
+        self.skip_unchanged = Some(unchanged);
+        self
+    }
+
+    /// The root of the shared rustdoc site.
+    pub fn dest(&self) -> &Path {
+        &self.dest
+    }
+
+    /// The documentation directories to merge, including those of the workspaces, along with the
+    /// crates that may be taken from each workspace.
+    fn collect_sources(&self) -> (Vec<PathBuf>, BTreeMap<PathBuf, BTreeSet<String>>) {
+        let mut sources = self.sources.clone();
+        let mut restrictions = BTreeMap::<PathBuf, BTreeSet<String>>::new();
+        for workspace in &self.workspaces {
+            let doc_dir = workspace.doc_dir();
+            if !sources.contains(&doc_dir) {
+                sources.push(doc_dir.clone());
+            }
+            restrictions
+                .entry(doc_dir)
+                .or_default()
+                .extend(workspace.crates.iter().cloned());
+        }
+        (sources, restrictions)
+    }
+
+    /// Run the merge.
+    ///
+    /// The merged site is built in a staging directory next to the destination, and only moved
+    /// into place once the merge has succeeded. The site that was there before is kept next to
+    /// it, in `.<dest>.previous`, or `.<dest>.<version>.previous` for one version of a site.
+    /// Neither is ever inside the destination.
+    pub fn execute(&self) -> Result<MergeReport> {
+        let Some(version) = &self.version else {
+            if self.latest.is_some() {
+                bail!(
+                    "A `latest` alias can only be kept for a site with several versions; set \
+                     the version to merge"
+                );
+            }
+            return self.run(&self.dest);
+        };
+        versions::check_name(version)?;
+        let versioned = Self {
+            dest: self.dest.join(version),
+            ..self.clone()
+        };
+        let mut report = versioned.run(&self.dest)?;
+        for rel in &mut report.stale {
+            *rel = Path::new(version).join(&*rel);
+        }
+        report.versions = versions::with(&self.dest, version)?;
+        if !self.dry_run {
+            versions::record(&self.dest, &report.versions)?;
+        }
+        if let Some(latest) = self.latest {
+            let newest = report.versions[0].clone();
+            if !self.dry_run {
+                latest.update(&self.dest, &newest)?;
+            }
+            report.latest = Some(newest);
+        }
+        Ok(report)
+    }
+
+    /// Run the merge into the destination, through a staging directory next to `site`: the
+    /// destination itself, or the site that it is one version of.
+    fn run(&self, site: &Path) -> Result<MergeReport> {
+        if self.dry_run {
+            return self.merge().map(|(report, _)| report);
+        }
+        if let Some(build) = &self.build {
+            build.run_all(&self.workspaces)?;
+        }
+
+        let staging = Staging::create(&self.dest, site)?;
+        let staged = Self {
+            dest: staging.path().to_owned(),
+            build: None,
+            link_dirs: self.link_dirs(site)?,
+            ..self.clone()
+        };
+        let (mut report, generated) = match staged.merge() {
+            Ok(merged) => merged,
+            Err(mut err) => {
+                if let Some(err) = err.downcast_mut::<MergeError>() {
+                    err.relocate(staging.path(), &self.dest);
+                }
+                staging.discard();
+                return Err(err);
+            }
+        };
+        if self.skip_unchanged.is_some() {
+            let kept = staging.keep_unchanged(&generated)?;
+            report.files_written -= kept;
+            report.files_skipped += kept;
+        }
+        for owner in report.crates.values_mut() {
+            if owner == staging.path() {
+                owner.clone_from(&self.dest);
+            }
+        }
+        relocate(&mut report.rustdoc_versions, staging.path(), &self.dest);
+        report.index = Some(self.dest.join("index.html"));
+        report.previous = staging.commit()?;
+        Ok(report)
+    }
+
+    /// The directories of the live site that the files copied into the destination, one version
+    /// of `site` or all of it, can be linked to: the destination itself, whose files they
+    /// replace, and the other versions of the site. Symbolic links only ever point at files
+    /// copied by the same merge.
+    fn link_dirs(&self, site: &Path) -> Result<Vec<LinkDir>> {
+        let mut dirs = Vec::new();
+        // a symbolic link to the file it replaces would point at itself once the destination is
+        // in place, and one into another version would break once that version is merged again
+        // or pruned
+        if matches!(self.link_mode, LinkMode::Copy | LinkMode::Symlink) {
+            return Ok(dirs);
+        }
+        dirs.push(LinkDir {
+            path: self.dest.clone(),
+            from_dest: PathBuf::new(),
+        });
+        if site != self.dest {
+            for version in versions::read(site)? {
+                let path = site.join(&version);
+                if path != self.dest {
+                    dirs.push(LinkDir {
+                        path,
+                        from_dest: Path::new("..").join(version),
+                    });
+                }
+            }
+        }
+        Ok(dirs)
+    }
+
+    /// Merge the sources into the destination, in place. Returns the report, along with the files
+    /// (relative to the destination) that were generated rather than copied.
+    fn merge(&self) -> Result<(MergeReport, BTreeSet<PathBuf>)> {
+        let (sources, restrictions) = self.collect_sources();
+
+        // Sanity check: Does the source directory exist?
+        if self.incremental && sources.is_empty() {
+            bail!("At least one documentation path must be passed for adding to a site");
+        }
+        // a workspace documents all of its members into one directory, which is worth merging
+        // on its own
+        if !self.incremental && self.workspaces.is_empty() && sources.len() < 2 {
+            bail!("At least two documentation paths must be passed for merging");
+        }
+        let mut report = MergeReport::default();
+
+        // when adding to an existing site, the crates already in it are kept unless one of the
+        // sources documents them again. Only a site that does not exist yet starts out empty: one
+        // whose search index cannot be read would otherwise lose every crate already in it.
+        let base = if self.incremental && !is_empty_dir(&self.dest)? {
+            Some(search_index::detect(&self.dest)?)
+        } else {
+            None
+        };
+        let existing = match &base {
+            Some((format, path)) => format.read(path)?,
+            None => SearchIndex::new(),
+        };
+
+        // the crates in the site before the merge, for telling which of them were renamed
+        let previous = match (self.detect_moves, &base) {
+            (false, _) => SearchIndex::new(),
+            (true, Some(_)) => existing.clone(),
+            (true, None) => match search_index::detect(&self.dest) {
+                Ok((format, path)) => format.read(&path).unwrap_or_default(),
+                Err(_) => SearchIndex::new(),
+            },
+        };
+        for path in self.redirects.keys().chain(self.redirects.values()) {
+            redirects::dir(path)?;
+        }
+
+        // parse the search index of every source, making sure they were all built by the same
+        // generation of rustdoc. The index is written to the same place in the site as it was
+        // found in them.
+        let mut format: Option<(&dyn SearchIndexFormat, PathBuf)> = base
+            .as_ref()
+            .map(|(format, path)| (*format, relative(&self.dest, path)));
+        let mut found = BTreeMap::<String, Vec<(PathBuf, JsonValue)>>::new();
+        let mut skipped = BTreeSet::new();
+        for docs_path in &sources {
+            let (src_format, path) = search_index::detect(docs_path)?;
+            match &format {
+                Some((format, _)) if format.name() != src_format.name() => {
+                    return Err(MergeError::UnsupportedFormat {
+                        path,
+                        format: src_format.name(),
+                        reason: format!(
+                            "this source uses the {} search index, but other sources (or the \
+                             destination) use {}; document them all with the same toolchain",
+                            src_format.name(),
+                            format.name()
+                        ),
+                    }
+                    .into())
+                }
+                Some(_) => {}
+                None => format = Some((src_format, relative(docs_path, &path))),
+            }
+            let index = src_format.read(&path)?;
+            if let Some(version) = rustdoc_version::detect(docs_path, index.keys()) {
+                report.rustdoc_versions.insert(docs_path.clone(), version);
+            }
+            for (crate_name, crate_data) in index {
+                if restrictions
+                    .get(docs_path)
+                    .is_some_and(|crates| !crates.contains(&crate_name))
+                {
+                    skipped.insert(crate_name);
+                    continue;
+                }
+                found
+                    .entry(crate_name)
+                    .or_default()
+                    .push((docs_path.clone(), crate_data));
+            }
+        }
+        let (format, index_rel) = format.expect("at least one source");
+        if let Some(version) = rustdoc_version::detect(&self.dest, existing.keys()) {
+            report.rustdoc_versions.insert(self.dest.clone(), version);
+        }
+
+        // pages documented by different versions of rustdoc use different assets
+        let mut rewrites = Vec::new();
+        let versions = report.rustdoc_versions.values().collect::<BTreeSet<_>>();
+        if versions.len() > 1 {
+            match self.on_version_mismatch {
+                VersionMismatch::Warn => {}
+                VersionMismatch::Error => {
+                    return Err(MergeError::VersionMismatch {
+                        versions: report.rustdoc_versions.clone(),
+                    }
+                    .into())
+                }
+                VersionMismatch::Rewrite => {
+                    let chosen = rustdoc_version::newest(&report.rustdoc_versions, &sources)
+                        .expect("sources have versions");
+                    let chosen_version = &report.rustdoc_versions[&chosen];
+                    for src in &sources {
+                        match report.rustdoc_versions.get(src) {
+                            Some(version) if version != chosen_version => {
+                                rewrites.push(AssetRewrite::new(
+                                    src,
+                                    version,
+                                    &chosen,
+                                    chosen_version,
+                                )?);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+
+        // decide which source each crate is taken from
+        let mut crates = SearchIndex::new();
+        for (crate_name, candidates) in found {
+            if !self.filter.allows(&crate_name) {
+                report.excluded.insert(crate_name);
+                continue;
+            }
+            let sources = candidates
+                .iter()
+                .map(|(src, _)| src.clone())
+                .collect::<Vec<_>>();
+            let chosen = self.on_conflict.resolve(&crate_name, &sources)?;
+            let (_, crate_data) = candidates
+                .into_iter()
+                .rfind(|(src, _)| *src == chosen)
+                .expect("chosen source is a candidate");
+            if !chosen.join(&crate_name).is_dir() {
+                return Err(MergeError::InvalidFile {
+                    path: chosen.join(&crate_name),
+                    crate_name: Some(crate_name),
+                    reason: "the search index lists this crate, but its documentation is missing"
+                        .to_owned(),
+                }
+                .into());
+            }
+            if sources.len() > 1 {
+                report.conflicts.push(Conflict {
+                    crate_name: crate_name.clone(),
+                    sources,
+                    chosen: chosen.clone(),
+                });
+            }
+            crates.insert(crate_name.clone(), crate_data);
+            report.crates.insert(crate_name, chosen);
+        }
+
+        report.excluded.extend(
+            skipped
+                .into_iter()
+                .filter(|name| !report.crates.contains_key(name)),
+        );
+
+        // create destination if it doesnt exist
+        if !self.dry_run {
+            fs::create_dir_all(&self.dest).map_err(MergeError::io(&self.dest))?;
+        }
+
+        // keep the crates that are already in the site, and clear out the old documentation of
+        // the ones that are being updated or are now left out
+        for (crate_name, crate_data) in existing {
+            if !self.filter.allows(&crate_name) {
+                if !self.dry_run {
+                    remove_crate_dirs(&self.dest, &crate_name)?;
+                }
+                report.excluded.insert(crate_name);
+                continue;
+            }
+            match report.crates.entry(crate_name) {
+                Entry::Occupied(entry) => {
+                    if !self.dry_run {
+                        remove_crate_dirs(&self.dest, entry.key())?;
+                    }
+                    report.updated.insert(entry.key().clone());
+                }
+                Entry::Vacant(entry) => {
+                    crates.insert(entry.key().clone(), crate_data);
+                    entry.insert(self.dest.clone());
+                }
+            }
+        }
+
+        if self.detect_moves {
+            report.redirects = redirects::detect(&previous, &crates);
+        }
+        report.redirects.extend(self.redirects.clone());
+
+        // Copy the each subdirectory in the source to the destination (but not the files). The
+        // implementor directories are shared between crates, and are merged separately below.
+        // Everything belonging to a crate is only copied from the source it is taken from.
+        // Files that several sources share, such as the pages in static.files/, are taken from
+        // the last of them, so that the result does not depend on the order they are copied in.
+        let mut files = BTreeMap::new();
+        for src in &sources {
+            for rel in taken_from(src, &report)? {
+                for file in files_below(src, &rel)? {
+                    files.insert(self.dest.join(&file), src.join(file));
+                }
+                report.copies.push((src.clone(), rel));
+            }
+        }
+        let mut rewritten = Vec::new();
+        if !self.dry_run {
+            let mut files = files
+                .into_iter()
+                .map(|(to, from)| (from, to))
+                .collect::<Vec<_>>();
+            // the pages of sources documented by older versions of rustdoc, and those linking to
+            // docs.rs pages of crates in the site, are rewritten on their way over instead of
+            // being copied. Pages with local links are kept as they are found here, so that they
+            // are only read once.
+            let links = docs_rs::link_regex();
+            files.retain(|(from, to)| {
+                if from.extension().is_none_or(|ext| ext != "html") {
+                    return true;
+                }
+                let rewrite = rewrites
+                    .iter()
+                    .find(|rewrite| from.starts_with(&rewrite.src));
+                let local = if self.local_links {
+                    let rel = to.strip_prefix(&self.dest).unwrap_or(to);
+                    fs::read_to_string(from)
+                        .ok()
+                        .and_then(|page| docs_rs::localize(&page, rel, &report.crates, &links))
+                } else {
+                    None
+                };
+                if rewrite.is_none() && local.is_none() {
+                    return true;
+                }
+                rewritten.push((rewrite, from.clone(), to.clone(), local));
+                false
+            });
+            let (files, twins) = match self.link_mode {
+                LinkMode::Copy => (files, Vec::new()),
+                _ => {
+                    // every page of one version of a site gets the version menu below, which
+                    // names the version, so a page is never the same as one already in the site
+                    // and cannot share its contents with another page of the merge
+                    let (pages, files) = files.into_iter().partition::<Vec<_>, _>(|(from, _)| {
+                        self.version.is_some() && from.extension().is_some_and(|ext| ext == "html")
+                    });
+                    let (mut copies, twins) = link::dedup(files, &self.dest, &self.link_dirs)?;
+                    copies.extend(pages);
+                    (copies, twins)
+                }
+            };
+            let copied = fsutil::copy_files(&files, self.jobs, self.skip_unchanged)?;
+            report.bytes_copied = copied.bytes;
+            report.files_written = copied.files;
+            report.files_skipped = copied.skipped;
+            for twin in &twins {
+                self.link_mode.link(twin)?;
+            }
+            report.files_linked = twins.len();
+            for (rewrite, from, to, local) in &mut rewritten {
+                let mut page = match local.take() {
+                    Some(page) => page,
+                    None => fs::read_to_string(&*from).map_err(MergeError::io(from))?,
+                };
+                if let Some(rewrite) = rewrite {
+                    page = rewrite.apply(&page);
+                }
+                fsutil::write_file(to, page)?;
+            }
+            report.files_written += rewritten.len();
+        }
+
+        // the shared files already in the site are merged as if the site was the first source
+        let shared_sources = match base {
+            Some(_) => [&self.dest].into_iter().chain(&sources).cloned().collect(),
+            None => sources.clone(),
+        };
+        let index_files = format.render(&self.dest.join(&index_rel), &crates)?;
+        let mut generated = implementors::files(&shared_sources)?;
+        generated.extend(src_files::file_name(&shared_sources).map(PathBuf::from));
+        generated.extend(["crates.js", "index.html"].map(PathBuf::from));
+        generated.extend(
+            index_files
+                .iter()
+                .map(|(path, _)| relative(&self.dest, path)),
+        );
+        let rewritten = rewritten
+            .into_iter()
+            .filter_map(|(_, _, to, _)| Some(to.strip_prefix(&self.dest).ok()?.to_owned()))
+            .collect::<Vec<_>>();
+        let index_path = self.dest.join("index.html");
+        report.index = Some(index_path.clone());
+
+        if !self.dry_run {
+            report.files_written += generated.len();
+
+            // union the implementors listed by each source
+            let links = self.local_links.then(docs_rs::link_regex);
+            implementors::merge(&shared_sources, &report, &self.dest, links.as_ref())?;
+
+            // union the source browser index
+            src_files::merge(&shared_sources, &report, &self.dest)?;
+
+            // Write the crates.js file, in the shape of that of the first source that has one.
+            let fragments = shared_sources
+                .iter()
+                .find(|src| src.join("crates.js").is_file())
+                .is_some_and(|src| crates_js_has_fragments(src));
+            write_crates_js(&self.dest, crates.keys(), fragments)?;
+
+            // write the search index in the same format it was read in, in place of any other
+            // generation of it that the site was documented with before
+            search_index::remove_replaced(&self.dest, &index_rel)?;
+            format.write(&self.dest.join(&index_rel), &index_files)?;
+
+            // write the landing page, or send readers straight to the index crate. Earlier
+            // versions symlinked the index crate's page here, so make sure not to write through
+            // the link.
+            if index_path.is_symlink() {
+                fs::remove_file(&index_path).map_err(MergeError::io(&index_path))?;
+            }
+            match self.index_crate.as_deref() {
+                Some(index_crate) => fsutil::write_file(
+                    &index_path,
+                    html::redirect_page(&format!("{index_crate}/index.html")),
+                )?,
+                None => {
+                    let title = self.title.as_deref().unwrap_or(landing::DEFAULT_TITLE);
+                    landing::write(&self.dest, title, &self.theme, report.crates.keys())?;
+                }
+            }
+        }
+
+        // every page gets the menu for switching to the other versions of the site
+        let mut versioned = Vec::new();
+        if let (Some(version), false) = (&self.version, self.dry_run) {
+            for rel in fsutil::walk_files(&self.dest)? {
+                if rel.extension().is_none_or(|ext| ext != "html") {
+                    continue;
+                }
+                let path = self.dest.join(&rel);
+                let page = fs::read_to_string(&path).map_err(MergeError::io(&path))?;
+                if let Some(page) = versions::inject(&page, &rel, version) {
+                    fsutil::write_file(&path, page)?;
+                    versioned.push(rel);
+                }
+            }
+        }
+
+        // the files the merge produced; everything else is left over from earlier merges
+        let mut expected = BTreeSet::new();
+        if self.prune.is_some() || !report.redirects.is_empty() {
+            expected.clone_from(&generated);
+            for (src, rel) in &report.copies {
+                expected.extend(files_below(src, rel)?);
+            }
+            if base.is_some() {
+                for rel in kept_in(&self.dest, &report)? {
+                    expected.extend(files_below(&self.dest, &rel)?);
+                }
+            }
+        }
+
+        // send readers of the crates and modules that moved on to their new pages
+        if !self.dry_run && !report.redirects.is_empty() {
+            let redirected = redirects::write(&self.dest, &report.redirects, &expected)?;
+            report.files_written += redirected.len();
+            expected.extend(redirected.iter().cloned());
+            generated.extend(redirected);
+        }
+
+        if let (Some(prune), true) = (self.prune, self.dest.is_dir()) {
+            report.stale = prune::stale_files(&self.dest, &expected)?;
+            // a dry run leaves the old search index in place, which the merge would replace
+            if self.dry_run {
+                report
+                    .stale
+                    .retain(|rel| !search_index::replaces(&index_rel, rel));
+            }
+            if prune == Prune::Delete && !self.dry_run {
+                prune::remove(&self.dest, &report.stale)?;
+            }
+        }
+
+        generated.extend(rewritten);
+        generated.extend(versioned);
+        Ok((report, generated))
+    }
+}
+
+/// The directories and files that `src` contributes to the merged site, relative to `src`.
+///
+/// This is every top-level directory (or, for the directories holding one subdirectory per
+/// crate, every subdirectory) that does not belong to a crate taken from another source or left
+/// out of the merge, and every top-level HTML page. The implementor directories and the
+/// `search.index/` directory are merged separately.
+fn taken_from(src: &Path, report: &MergeReport) -> Result<Vec<PathBuf>> {
+    let owned_by = |name: &OsStr| name.to_str().is_none_or(|name| report.takes(name, src));
+    let mut entries = Vec::new();
+    for entry in src.read_dir().map_err(MergeError::io(src))? {
+        let entry = entry.map_err(MergeError::io(src))?;
+        let file_name = entry.file_name();
+        let path = entry.path();
+        let Some(name) = file_name.to_str() else {
+            return Err(MergeError::NonUtf8Path { path }.into());
+        };
+        if implementors::DIRS.contains(&name) || name == stringdex::DIR {
+            continue;
+        }
+        if PER_CRATE_DIRS.contains(&name) {
+            for entry in path.read_dir().map_err(MergeError::io(&path))? {
+                let entry = entry.map_err(MergeError::io(&path))?;
+                if owned_by(&entry.file_name()) {
+                    entries.push(Path::new(&file_name).join(entry.file_name()));
+                }
+            }
+            continue;
+        }
+        if path.is_dir() && owned_by(&file_name) {
+            entries.push(PathBuf::from(name));
+        }
+        if name.ends_with(".html") {
+            entries.push(PathBuf::from(name));
+        }
+    }
+    entries.sort();
+    Ok(entries)
+}
+
+/// The directories and files of an existing site at `dest` that are kept when adding to it,
+/// relative to `dest`.
+///
+/// These are the directories of the crates that stay in the site, the top-level pages, and the
+/// shared directories such as `static.files/` that the pages of those crates may still use.
+/// Directories of crates that are no longer in the site are left out.
+fn kept_in(dest: &Path, report: &MergeReport) -> Result<Vec<PathBuf>> {
+    let kept = |name: &OsStr| {
+        name.to_str()
+            .and_then(|name| report.crates.get(name))
+            .is_some_and(|owner| owner == dest)
+    };
+    let mut entries = Vec::new();
+    for entry in dest.read_dir().map_err(MergeError::io(dest))? {
+        let entry = entry.map_err(MergeError::io(dest))?;
+        let file_name = entry.file_name();
+        let path = entry.path();
+        if implementors::DIRS.iter().any(|dir| file_name == *dir) || file_name == stringdex::DIR {
+            continue;
+        }
+        if PER_CRATE_DIRS.iter().any(|dir| file_name == *dir) {
+            for entry in path.read_dir().map_err(MergeError::io(&path))? {
+                let entry = entry.map_err(MergeError::io(&path))?;
+                if kept(&entry.file_name()) {
+                    entries.push(Path::new(&file_name).join(entry.file_name()));
+                }
+            }
+            continue;
+        }
+        // every crate's directory has an index.html, but shared directories do not
+        let is_crate = path.join("index.html").is_file();
+        if !path.is_dir() || kept(&file_name) || !is_crate {
+            entries.push(PathBuf::from(file_name));
+        }
+    }
+    entries.sort();
+    Ok(entries)
+}
+
+/// `path`, relative to the directory `dir` it is in.
+fn relative(dir: &Path, path: &Path) -> PathBuf {
+    path.strip_prefix(dir).unwrap_or(path).to_owned()
+}
+
+/// Whether `path` is missing, or an empty directory.
+fn is_empty_dir(path: &Path) -> Result<bool> {
+    match fs::read_dir(path) {
+        Ok(mut entries) => Ok(entries.next().is_none()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(true),
+        Err(err) => Err(MergeError::io(path)(err).into()),
+    }
+}
+
+/// Key the rustdoc version found in the staging directory `from` under the destination `to`.
+fn relocate(versions: &mut BTreeMap<PathBuf, String>, from: &Path, to: &Path) {
+    if let Some(version) = versions.remove(from) {
+        versions.insert(to.to_owned(), version);
+    }
+}
+
+/// Every file at or below `rel` in `root`, relative to `root`.
+fn files_below(root: &Path, rel: &Path) -> Result<Vec<PathBuf>> {
+    let path = root.join(rel);
+    if !path.is_dir() {
+        return Ok(vec![rel.to_owned()]);
+    }
+    Ok(fsutil::walk_files(&path)?
+        .into_iter()
+        .map(|file| rel.join(file))
+        .collect())
+}
+
+/// Write the list of crates in the site at `dest` to its crates.js file.
+///
+/// With `fragments`, the file ends with the comment that rustdoc 1.83 and later need for
+/// documenting more crates into the site, like the crates.js they write.
+pub(crate) fn write_crates_js<'a>(
+    dest: &Path,
+    crates: impl IntoIterator<Item = &'a String>,
+    fragments: bool,
+) -> Result<()> {
+    const HEAD: &str = "window.ALL_CRATES = [";
+    let names = crates
+        .into_iter()
+        .map(|name| format!("\"{name}\""))
+        .collect::<Vec<_>>();
+    let (body, comment) = js::join_fragments(HEAD.len(), &names);
+    let mut out = format!("{HEAD}{body}];");
+    if fragments {
+        out.push('\n');
+        out.push_str(&comment);
+    }
+    fsutil::write_file(&dest.join("crates.js"), out)
+}
+
+/// Whether the crates.js file in the documentation directory `dir` ends with a fragment comment.
+pub(crate) fn crates_js_has_fragments(dir: &Path) -> bool {
+    fs::read_to_string(dir.join("crates.js"))
+        .is_ok_and(|content| content.contains("\n//{\"start\""))
+}
+
+/// Delete the directories holding the documentation of `crate_name` from the site at `dest`.
+pub(crate) fn remove_crate_dirs(dest: &Path, crate_name: &str) -> Result<()> {
+    for dir in PER_CRATE_DIRS.iter().map(Path::new).chain([Path::new("")]) {
+        let path = dest.join(dir).join(crate_name);
+        if path.is_dir() {
+            fs::remove_dir_all(&path).map_err(MergeError::io(&path))?;
+        }
+    }
+    Ok(())
+}
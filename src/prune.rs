@@ -0,0 +1,63 @@
+//! Removal of stale files that a merge no longer produces.
+//!
+//! The merge only ever adds or overwrites files, so pages of renamed modules and removed crates
+//! would otherwise stay in the destination, still linkable and indexed by search engines.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::{fsutil, MergeError};
+
+/// What to do with the files in the destination that the merge did not produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Prune {
+    /// Only list the stale files.
+    DryRun,
+
+    /// Delete the stale files, along with the directories they leave empty.
+    Delete,
+}
+
+named_options!(Prune, "prune mode", {
+    DryRun => "dry-run",
+    Delete => "delete",
+});
+
+/// List the files below `dest` that are not in `expected`, as paths relative to `dest`.
+pub(crate) fn stale_files(dest: &Path, expected: &BTreeSet<PathBuf>) -> Result<Vec<PathBuf>> {
+    Ok(fsutil::walk_files(dest)?
+        .into_iter()
+        .filter(|rel| !expected.contains(rel))
+        .collect())
+}
+
+/// Delete `files` (relative to `dest`), and then every directory they leave empty.
+pub(crate) fn remove(dest: &Path, files: &[PathBuf]) -> Result<()> {
+    let mut dirs = BTreeSet::new();
+    for rel in files {
+        let path = dest.join(rel);
+        fs::remove_file(&path).map_err(MergeError::io(&path))?;
+        dirs.extend(
+            rel.ancestors()
+                .skip(1)
+                .filter(|dir| !dir.as_os_str().is_empty()),
+        );
+    }
+    // deepest first, so that parents are only looked at once their children are gone
+    for dir in dirs.into_iter().rev() {
+        let path = dest.join(dir);
+        if fs::read_dir(&path)
+            .map_err(MergeError::io(&path))?
+            .next()
+            .is_none()
+        {
+            fs::remove_dir(&path).map_err(MergeError::io(&path))?;
+        }
+    }
+    Ok(())
+}
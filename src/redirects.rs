@@ -0,0 +1,399 @@
+//! Redirect pages for crates and modules that moved between merges.
+//!
+//! When a crate is renamed, or a module moves, every link to its old pages breaks. For each
+//! redirect from an old path to a new one, a page is written at the old location of every page
+//! below the new path, sending readers on to it.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+use jzon::JsonValue;
+
+use crate::search_index::SearchIndex;
+use crate::{fsutil, html, link, stringdex};
+
+/// How much of a crate's items a crate added to the site must share with one that was removed for
+/// the two to be taken as the same crate, renamed. Items are compared by their paths in the crate.
+const RENAME_SIMILARITY: f64 = 0.5;
+
+/// The directory of a crate or module path such as `my_crate::module`, relative to the site.
+pub(crate) fn dir(path: &str) -> Result<PathBuf> {
+    let segments = path.split("::").collect::<Vec<_>>();
+    if segments.iter().any(|segment| {
+        segment.is_empty() || segment.starts_with('.') || segment.contains(['/', '\\'])
+    }) {
+        bail!("`{path}` is not a crate or module path, such as `my_crate::module`");
+    }
+    Ok(segments.iter().collect())
+}
+
+/// Find the crates that were renamed between the `previous` search index of the site and the
+/// `current` one, as a map from old names to new ones.
+///
+/// A crate that is no longer in the site is taken to have been renamed to a crate that is new to
+/// it if they share most of their items, and neither shares as many with another crate.
+pub(crate) fn detect(previous: &SearchIndex, current: &SearchIndex) -> BTreeMap<String, String> {
+    let removed = previous
+        .iter()
+        .filter(|(name, _)| !current.contains_key(*name))
+        .map(|(name, data)| (name, item_paths(data)))
+        .collect::<Vec<_>>();
+    let added = current
+        .iter()
+        .filter(|(name, _)| !previous.contains_key(*name))
+        .map(|(name, data)| (name, item_paths(data)))
+        .collect::<Vec<_>>();
+    let mut candidates = Vec::new();
+    for (old_name, old_items) in &removed {
+        for (new_name, new_items) in &added {
+            let similarity = similarity(old_items, new_items);
+            if similarity >= RENAME_SIMILARITY {
+                candidates.push((*old_name, *new_name, similarity));
+            }
+        }
+    }
+    candidates
+        .iter()
+        .filter(|&&(old_name, new_name, similarity)| {
+            !candidates.iter().any(|&(other_old, other_new, other)| {
+                (other_old == old_name) != (other_new == new_name) && other >= similarity
+            })
+        })
+        .map(|&(old_name, new_name, _)| (old_name.clone(), new_name.clone()))
+        .collect()
+}
+
+/// Write the redirect pages of `redirects`, from old crate or module paths to new ones, into the
+/// site at `dest`. Returns the pages written, relative to `dest`.
+///
+/// The pages in `produced` (relative to `dest`), which the merge wrote, are never replaced.
+pub(crate) fn write(
+    dest: &Path,
+    redirects: &BTreeMap<String, String>,
+    produced: &BTreeSet<PathBuf>,
+) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for (from, to) in redirects {
+        let (from_dir, to_dir) = (dir(from)?, dir(to)?);
+        if !dest.join(&to_dir).is_dir() {
+            bail!("Cannot redirect `{from}` to `{to}`, which is not in the merged site");
+        }
+        // a renamed crate's pages in the source browser moved too
+        let src_dir = Path::new("src").join(to);
+        if !from.contains("::") && !to.contains("::") && dest.join(&src_dir).is_dir() {
+            dirs.push((Path::new("src").join(from), src_dir));
+        }
+        dirs.push((from_dir, to_dir));
+    }
+
+    let mut written = Vec::new();
+    for (from_dir, to_dir) in dirs {
+        for rel in fsutil::walk_files(&dest.join(&to_dir))? {
+            if rel.extension().is_none_or(|ext| ext != "html") {
+                continue;
+            }
+            let page = from_dir.join(&rel);
+            if produced.contains(&page) {
+                continue;
+            }
+            let url =
+                link::relative_path(page.parent().unwrap_or(Path::new("")), &to_dir.join(&rel));
+            fsutil::write_file(
+                &dest.join(&page),
+                html::redirect_page(&url.to_string_lossy().replace('\\', "/")),
+            )?;
+            written.push(page);
+        }
+    }
+    Ok(written)
+}
+
+/// The paths of the items a crate's search index data lists, relative to the crate, such as
+/// `module::Type` or `Type::method`.
+///
+/// The methods of trait implementations are left out: the `clone`, `into` or `type_id` methods of
+/// derived and blanket implementations are found in nearly every crate, and say nothing about it.
+/// Only the data read from a `search.index/` directory tells which trait an item implements, so
+/// for the `search-index.js` of earlier versions of rustdoc every item that belongs to a type or
+/// trait is left out.
+fn item_paths(data: &JsonValue) -> BTreeSet<String> {
+    if data["e"].is_array() {
+        table_item_paths(data)
+    } else {
+        listed_item_paths(data)
+    }
+}
+
+/// The paths of the items in the table of rows read from a `search.index/` directory, whose
+/// entries give the module, parent and trait parent of each of the crate's own items.
+fn table_item_paths(data: &JsonValue) -> BTreeSet<String> {
+    let names = &data["n"];
+    let entries = &data["e"];
+    // the module and parents of an entry are rows plus one, or 0 for none
+    let field = |row: usize, field: usize| {
+        entries[row][field]
+            .as_usize()
+            .and_then(|target| target.checked_sub(1))
+    };
+    let mut paths = BTreeSet::new();
+    for row in 0..entries.len() {
+        // rows without an entry are items of other crates, and the crate itself has no module
+        let Some(mut module) = field(row, 2) else {
+            continue;
+        };
+        if field(row, 5).is_some() {
+            continue;
+        }
+        let mut segments = vec![names[row].as_str()];
+        if let Some(parent) = field(row, 4) {
+            segments.push(names[parent].as_str());
+        }
+        while let Some(outer) = field(module, 2) {
+            if segments.len() > entries.len() {
+                break;
+            }
+            segments.push(names[module].as_str());
+            module = outer;
+        }
+        if let Some(segments) = segments.into_iter().rev().collect::<Option<Vec<_>>>() {
+            paths.insert(segments.join("::"));
+        }
+    }
+    paths
+}
+
+/// The paths of the items listed in the `search-index.js` of rustdoc 1.90 and earlier, which
+/// gives the module path of each item and the type or trait it belongs to, if any.
+fn listed_item_paths(data: &JsonValue) -> BTreeSet<String> {
+    let parents = parents(&data["i"]);
+    // the module path of an item is listed either with every item, left empty when it is that of
+    // the item before, or only with the first item of every run of items in the same module
+    let mut modules = BTreeMap::new();
+    let mut module = "";
+    for (i, path) in data["q"].members().enumerate() {
+        match path.as_str() {
+            Some("") => {}
+            Some(path) => module = path,
+            None => match (path[0].as_usize(), path[1].as_str()) {
+                (Some(i), Some(path)) => {
+                    modules.insert(i, path);
+                    continue;
+                }
+                _ => continue,
+            },
+        }
+        modules.insert(i, module);
+    }
+    data["n"]
+        .members()
+        .enumerate()
+        .filter(|(i, _)| parents.get(*i).is_none_or(|parent| *parent == 0))
+        .filter_map(|(i, name)| {
+            let name = name.as_str()?;
+            let module = modules
+                .range(..=i)
+                .next_back()
+                .map_or("", |(_, path)| *path);
+            Some(match module.split_once("::") {
+                Some((_, module)) => format!("{module}::{name}"),
+                None => name.to_owned(),
+            })
+        })
+        .collect()
+}
+
+/// The parent of every item in the `search-index.js` of rustdoc 1.90 and earlier, as an index
+/// into its list of parents plus one, or 0 for none.
+///
+/// Later versions write them as self-terminating hex numbers, where a digit from `0` stands for
+/// one of the last sixteen numbers that were not 0, the most recent first.
+fn parents(value: &JsonValue) -> Vec<usize> {
+    let Some(encoded) = value.as_str() else {
+        return value
+            .members()
+            .map(|parent| parent.as_usize().unwrap_or(0))
+            .collect();
+    };
+    let mut parents = Vec::new();
+    let mut recent = VecDeque::new();
+    let mut bytes = encoded.as_bytes();
+    while let Some(&c) = bytes.first() {
+        let parent = match c {
+            b'0'..=b'?' => {
+                bytes = &bytes[1..];
+                recent.get(usize::from(c - b'0')).copied().unwrap_or(0)
+            }
+            _ => {
+                let Some((parent, len)) = stringdex::read_signed_vlqhex(bytes) else {
+                    break;
+                };
+                bytes = &bytes[len..];
+                let parent = usize::try_from(parent).unwrap_or(0);
+                if parent != 0 {
+                    recent.push_front(parent);
+                    recent.truncate(16);
+                }
+                parent
+            }
+        };
+        parents.push(parent);
+    }
+    parents
+}
+
+/// How much two sets of item paths overlap, from 0 (not at all) to 1 (they are the same).
+fn similarity(a: &BTreeSet<String>, b: &BTreeSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::search_index;
+
+    /// The search index data of the `alpha` and `beta` fixtures in each generation of the index,
+    /// by the rustdoc version that wrote it.
+    fn generations() -> Vec<(&'static str, SearchIndex, SearchIndex)> {
+        // rustdoc 1.75 lists the module path of every item, and 1.90 writes the parents as hex
+        // numbers and the items without a description as a bitmap
+        let alpha = jzon::object! {
+            "t": [3, 11, 11, 0, 3],
+            "n": ["Thing", "clone", "into", "sub", "Inner"],
+            "q": ["alpha", "", "", "", "alpha::sub"],
+            "i": [0, 1, 1, 0, 0],
+            "p": [[3, "Thing"]],
+        };
+        let beta = jzon::object! {
+            "t": [3, 11],
+            "n": ["Other", "clone"],
+            "q": ["beta", ""],
+            "i": [0, 1],
+            "p": [[3, "Other"]],
+        };
+        let json_map = |name: &str, data: &str| {
+            SearchIndex::from([(name.to_owned(), jzon::parse(data).unwrap())])
+        };
+        let mut generations = vec![
+            (
+                "1.75",
+                SearchIndex::from([("alpha".to_owned(), alpha)]),
+                SearchIndex::from([("beta".to_owned(), beta)]),
+            ),
+            (
+                "1.90",
+                json_map(
+                    "alpha",
+                    r#"{"t":"FNNNNNNNCNNNN","n":["Thing","borrow","borrow_mut","clone","clone_into","clone_to_uninit","from","into","sub","to_owned","try_from","try_into","type_id"],"q":[[0,"alpha"],[13,"core::result"],[14,"core::any"]],"i":"`f000000`0000","e":"OzAAAAEAAAgAAgACAAQACgADAA=="}"#,
+                ),
+                json_map(
+                    "beta",
+                    r#"{"t":"FNNNNNNNNNNN","n":["Other","borrow","borrow_mut","clone","clone_into","clone_to_uninit","from","into","to_owned","try_from","try_into","type_id"],"q":[[0,"beta"],[12,"core::result"],[13,"core::any"]],"i":"`f0000000000","e":"OzAAAAEAAAgAAgACAAQACQADAA=="}"#,
+                ),
+            ),
+        ];
+        let stringdex = |version: &'static str| {
+            let read = |docs: &str| {
+                let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("tests/fixtures")
+                    .join(format!("rustdoc-{version}"))
+                    .join(docs);
+                let (format, path) = search_index::detect(&dir).unwrap();
+                format.read(&path).unwrap()
+            };
+            (version, read("alpha"), read("beta"))
+        };
+        generations.extend([stringdex("1.95"), stringdex("1.97")]);
+        generations
+    }
+
+    /// `index`, with its only crate renamed to `name`.
+    fn renamed(index: &SearchIndex, name: &str) -> SearchIndex {
+        index
+            .values()
+            .map(|data| (name.to_owned(), data.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn lists_the_items_of_a_crate_without_trait_implementations() {
+        for (version, alpha, _) in generations() {
+            let paths = item_paths(&alpha["alpha"]);
+            let expected: &[&str] = match version {
+                "1.75" => &["Thing", "sub", "sub::Inner"],
+                _ => &["Thing", "sub"],
+            };
+            assert_eq!(
+                paths.iter().collect::<Vec<_>>(),
+                expected,
+                "rustdoc {version}"
+            );
+        }
+    }
+
+    #[test]
+    fn detects_a_renamed_crate() {
+        for (version, alpha, beta) in generations() {
+            let mut current = renamed(&alpha, "alpha_core");
+            current.extend(beta.clone());
+            let mut previous = alpha;
+            previous.extend(beta);
+            assert_eq!(
+                detect(&previous, &current),
+                BTreeMap::from([("alpha".to_owned(), "alpha_core".to_owned())]),
+                "rustdoc {version}"
+            );
+        }
+    }
+
+    #[test]
+    fn leaves_unrelated_crates_alone() {
+        for (version, alpha, beta) in generations() {
+            assert!(detect(&alpha, &beta).is_empty(), "rustdoc {version}");
+        }
+    }
+
+    #[test]
+    fn needs_one_clear_best_match() {
+        for (version, alpha, _) in generations() {
+            let mut current = renamed(&alpha, "alpha_core");
+            current.extend(renamed(&alpha, "alpha_std"));
+            assert!(detect(&alpha, &current).is_empty(), "rustdoc {version}");
+        }
+    }
+
+    #[test]
+    fn writes_a_redirect_for_every_page() {
+        let dest = tempfile::tempdir().unwrap();
+        for page in [
+            "alpha_core/index.html",
+            "alpha_core/sub/struct.Inner.html",
+            "alpha_core/sidebar-items.js",
+            "src/alpha_core/lib.rs.html",
+        ] {
+            fsutil::write_file(&dest.path().join(page), "").unwrap();
+        }
+        let redirects = BTreeMap::from([("alpha".to_owned(), "alpha_core".to_owned())]);
+        let produced = BTreeSet::from([PathBuf::from("alpha/index.html")]);
+
+        let mut written = write(dest.path(), &redirects, &produced).unwrap();
+        written.sort();
+        assert_eq!(
+            written,
+            ["alpha/sub/struct.Inner.html", "src/alpha/lib.rs.html"].map(PathBuf::from)
+        );
+        let page = fs::read_to_string(dest.path().join("alpha/sub/struct.Inner.html")).unwrap();
+        assert!(page.contains(r#"href="../../alpha_core/sub/struct.Inner.html""#));
+        assert!(!dest.path().join("alpha/index.html").exists());
+        assert!(!dest.path().join("alpha/sidebar-items.js").exists());
+
+        let missing = BTreeMap::from([("alpha".to_owned(), "gamma".to_owned())]);
+        assert!(write(dest.path(), &missing, &produced).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+//! Removal of crates from a merged site.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+
+use crate::merger::{crates_js_has_fragments, remove_crate_dirs, write_crates_js};
+use crate::search_index::{SearchIndex, SearchIndexFormat};
+use crate::src_files::SrcFiles;
+use crate::staging::Staging;
+use crate::{implementors, landing, search_index, versions, MergeError};
+
+/// Remove crates from the merged site at `dest`.
+///
+/// This deletes each crate's documentation and source pages, and drops it from the search index,
+/// the crate list, the source browser and the implementor lists of other crates' traits.
+///
+/// Like a merge, the removal is made in a staging directory and only moved into place once it has
+/// succeeded, so a site with a file that cannot be parsed is left as it was. The site that was
+/// there before is kept next to it, in `.<dest>.previous`.
+pub fn remove<I, S>(dest: &Path, crates: I) -> Result<()>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let crates = crates.into_iter().map(Into::into).collect::<BTreeSet<_>>();
+    let (format, index_path) = search_index::detect(dest)?;
+    let mut index = format.read(&index_path)?;
+    let missing = crates
+        .iter()
+        .filter(|name| !index.contains_key(*name))
+        .map(|name| format!("`{name}`"))
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        bail!(
+            "Not in the site at {}: {}",
+            dest.display(),
+            missing.join(", ")
+        );
+    }
+    index.retain(|name, _| !crates.contains(name));
+
+    let staging = Staging::create(dest, site_of(dest)?)?;
+    let staged = staging.path();
+    let index_path = staged.join(index_path.strip_prefix(dest)?);
+    if let Err(mut err) = remove_from(staged, &crates, format, &index_path, &index) {
+        if let Some(err) = err.downcast_mut::<MergeError>() {
+            err.relocate(staged, dest);
+        }
+        staging.discard();
+        return Err(err);
+    }
+    staging.commit()?;
+    Ok(())
+}
+
+/// Remove `crates` from the site at `dest`, writing the search `index` that is left without them
+/// to `index_path`.
+fn remove_from(
+    dest: &Path,
+    crates: &BTreeSet<String>,
+    format: &dyn SearchIndexFormat,
+    index_path: &Path,
+    index: &SearchIndex,
+) -> Result<()> {
+    format.write(index_path, &format.render(index_path, index)?)?;
+    write_crates_js(dest, index.keys(), crates_js_has_fragments(dest))?;
+    if let Some(mut src_files) = SrcFiles::find(dest)? {
+        src_files.crates.retain(|name, _| !crates.contains(name));
+        src_files.write(dest)?;
+    }
+    implementors::remove(dest, crates)?;
+    for crate_name in crates {
+        remove_crate_dirs(dest, crate_name)?;
+    }
+    landing::refresh(dest, index.keys())
+}
+
+/// The site that `dest` belongs to: its parent, if `dest` is one of the versions listed there,
+/// and otherwise `dest` itself.
+fn site_of(dest: &Path) -> Result<&Path> {
+    if let (Some(parent), Some(name)) = (dest.parent(), dest.file_name()) {
+        if !parent.as_os_str().is_empty()
+            && versions::read(parent)?
+                .iter()
+                .any(|version| name == version.as_str())
+        {
+            return Ok(parent);
+        }
+    }
+    Ok(dest)
+}
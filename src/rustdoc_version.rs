@@ -0,0 +1,140 @@
+//! Detection of the rustdoc version each source was documented with.
+//!
+//! Each version of rustdoc ships its own `static.files/` assets (with a hash in their names) and
+//! expects its own search index schema, so pages documented by different versions do not mix
+//! well in one site.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::MergeError;
+
+/// What to do when the sources were documented by different versions of rustdoc.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum VersionMismatch {
+    /// Merge them anyway, and report the versions.
+    #[default]
+    Warn,
+
+    /// Refuse to merge.
+    Error,
+
+    /// Rewrite the pages of the sources documented by older versions to use the assets of the
+    /// newest one.
+    Rewrite,
+}
+
+named_options!(VersionMismatch, "version mismatch policy", {
+    Warn => "warn",
+    Error => "error",
+    Rewrite => "rewrite",
+});
+
+/// Read the version of rustdoc that documented `crates` in the documentation directory `src`,
+/// such as `1.76.0 (07dca489a 2024-02-04)`.
+///
+/// rustdoc writes its version to every page, in the `data-rustdoc-version` attribute of the
+/// `rustdoc-vars` element. Versions older than 1.56 do not, so their version is unknown.
+pub(crate) fn detect<'a>(
+    src: &Path,
+    crates: impl IntoIterator<Item = &'a String>,
+) -> Option<String> {
+    let regex = Regex::new(r#"data-rustdoc-version="([^"]+)""#).expect("valid regex");
+    crates.into_iter().find_map(|crate_name| {
+        let page = fs::read_to_string(src.join(crate_name).join("index.html")).ok()?;
+        regex.captures(&page).map(|captures| captures[1].to_owned())
+    })
+}
+
+/// Out of the sources with a known rustdoc version, pick the one documented by the newest
+/// version. Ties go to the last of them.
+pub(crate) fn newest(versions: &BTreeMap<PathBuf, String>, sources: &[PathBuf]) -> Option<PathBuf> {
+    sources
+        .iter()
+        .filter_map(|src| Some((number(versions.get(src)?), src)))
+        .max_by_key(|(number, _)| *number)
+        .map(|(_, src)| src.clone())
+}
+
+/// The `major.minor.patch` numbers at the start of a version.
+fn number(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version
+        .split(|c: char| !c.is_ascii_digit())
+        .take(3)
+        .map(|part| part.parse().ok());
+    Some((parts.next()??, parts.next()??, parts.next()??))
+}
+
+/// Rewrites the pages of one source to use the assets of another.
+#[derive(Debug, Clone)]
+pub(crate) struct AssetRewrite {
+    /// The source whose pages are rewritten.
+    pub(crate) src: PathBuf,
+
+    /// Pairs of strings to replace, and what to replace them with.
+    replacements: Vec<(String, String)>,
+}
+
+impl AssetRewrite {
+    /// Map the asset names of `src`, documented by `version`, to those of `chosen`, documented
+    /// by `chosen_version`.
+    ///
+    /// Assets are matched by their names with the hash left out, such as `rustdoc.css` for
+    /// `static.files/rustdoc-b7b9f40b.css`.
+    pub(crate) fn new(
+        src: &Path,
+        version: &str,
+        chosen: &Path,
+        chosen_version: &str,
+    ) -> Result<Self> {
+        let chosen_assets = assets(chosen)?;
+        let mut replacements = assets(src)?
+            .into_iter()
+            .filter_map(|(key, name)| {
+                let chosen_name = chosen_assets.get(&key)?;
+                (*chosen_name != name).then(|| (name, chosen_name.clone()))
+            })
+            .collect::<Vec<_>>();
+        replacements.push((
+            format!("data-rustdoc-version=\"{version}\""),
+            format!("data-rustdoc-version=\"{chosen_version}\""),
+        ));
+        Ok(Self {
+            src: src.to_owned(),
+            replacements,
+        })
+    }
+
+    /// Rewrite the contents of an HTML page.
+    pub(crate) fn apply(&self, page: &str) -> String {
+        self.replacements
+            .iter()
+            .fold(page.to_owned(), |page, (from, to)| page.replace(from, to))
+    }
+}
+
+/// The assets in the `static.files/` directory of `src`, by their name without the hash.
+fn assets(src: &Path) -> Result<BTreeMap<String, String>> {
+    let dir = src.join("static.files");
+    if !dir.is_dir() {
+        return Ok(BTreeMap::new());
+    }
+    let regex = Regex::new(r"^(.+)-[0-9a-f]{8,}(\..+)$").expect("valid regex");
+    let mut assets = BTreeMap::new();
+    for entry in fs::read_dir(&dir).map_err(MergeError::io(&dir))? {
+        let name = entry.map_err(MergeError::io(&dir))?.file_name();
+        let Some(name) = name.to_str() else {
+            continue;
+        };
+        if let Some(captures) = regex.captures(name) {
+            assets.insert(format!("{}{}", &captures[1], &captures[2]), name.to_owned());
+        }
+    }
+    Ok(assets)
+}
@@ -0,0 +1,343 @@
+//! Readers and writers for the different generations of the rustdoc search index.
+//!
+//! rustdoc has changed the on-disk shape of its search index several times. Each shape is
+//! described by a [`SearchIndexFormat`], and [`detect`] figures out which one a documentation
+//! directory was built with.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use jzon::JsonValue;
+
+use crate::stringdex::{self, Codec, Stringdex005, Stringdex006};
+use crate::{fsutil, js, MergeError};
+
+/// The search index data of each crate, keyed by crate name.
+pub type SearchIndex = BTreeMap<String, JsonValue>;
+
+/// One generation of the rustdoc search index.
+pub trait SearchIndexFormat: Sync {
+    /// A short, human-readable description of the format.
+    fn name(&self) -> &'static str;
+
+    /// Find the search index of this format in a documentation directory, if there is one.
+    fn locate(&self, dir: &Path) -> Option<PathBuf>;
+
+    /// Read the search index at `path`.
+    fn read(&self, path: &Path) -> Result<SearchIndex>;
+
+    /// Lay out `index` as the files that hold it when it is written to `path`, with their
+    /// contents.
+    fn render(&self, path: &Path, index: &SearchIndex) -> Result<Vec<(PathBuf, Vec<u8>)>>;
+
+    /// Write the `files` that [`render`](Self::render) laid out for the search index at `_path`,
+    /// replacing it.
+    fn write(&self, _path: &Path, files: &[(PathBuf, Vec<u8>)]) -> Result<()> {
+        for (file, contents) in files {
+            fsutil::write_file(file, contents)?;
+        }
+        Ok(())
+    }
+}
+
+/// Every format doc-merge knows about, newest first.
+pub static FORMATS: &[&dyn SearchIndexFormat] = &[
+    &Stringdex::V0_0_6,
+    &Stringdex::V0_0_5,
+    &JsonMap,
+    &JsonObject,
+];
+
+/// Determine the search index format used by a documentation directory.
+///
+/// Returns the format along with the path of the search index itself.
+pub fn detect(dir: &Path) -> Result<(&'static dyn SearchIndexFormat, PathBuf)> {
+    FORMATS
+        .iter()
+        .find_map(|format| format.locate(dir).map(|path| (*format, path)))
+        .ok_or_else(|| {
+            match stringdex::find_root(dir) {
+                Some(path) => MergeError::UnsupportedFormat {
+                    path,
+                    format: "search.index/",
+                    reason: "this search index was written by a version of rustdoc that doc-merge \
+                             cannot merge; the search.index/ directory can only be merged when \
+                             it was written by rustdoc 1.94 through 1.98"
+                        .to_owned(),
+                },
+                None => MergeError::MissingSearchIndex {
+                    src: dir.to_owned(),
+                },
+            }
+            .into()
+        })
+}
+
+/// The `search-index.js` file written by rustdoc 1.76 through 1.90.
+///
+/// ```js
+/// var searchIndex = new Map(JSON.parse('[["crate",{...}],...]'));
+/// ```
+pub struct JsonMap;
+
+/// Everything in front of the first crate's data in a [`JsonMap`] search index.
+const HEAD: &str = "var searchIndex = new Map(JSON.parse('[";
+
+impl SearchIndexFormat for JsonMap {
+    fn name(&self) -> &'static str {
+        "search-index.js (Map)"
+    }
+
+    fn locate(&self, dir: &Path) -> Option<PathBuf> {
+        find_search_index_js(dir).filter(|path| {
+            fs::read_to_string(path).is_ok_and(|content| content.contains("new Map(JSON.parse("))
+        })
+    }
+
+    fn read(&self, path: &Path) -> Result<SearchIndex> {
+        let json = js::read_json_parse_call(path)?;
+        let mut index = SearchIndex::new();
+        for item in json.members() {
+            let crate_name = item[0]
+                .as_str()
+                .ok_or_else(|| MergeError::invalid(path, "search index entry has no crate name"))?;
+            index.insert(crate_name.to_owned(), item[1].clone());
+        }
+        Ok(index)
+    }
+
+    fn render(&self, path: &Path, index: &SearchIndex) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        let fragments = index
+            .iter()
+            .map(|(name, data)| {
+                js::escape_string(&jzon::array![name.as_str(), data.clone()].dump())
+            })
+            .collect::<Vec<_>>();
+        let (body, comment) = js::join_fragments(HEAD.len(), &fragments);
+        // rustdoc 1.83 and later find each crate's data through the comment at the end, which
+        // the earlier versions of this format do not have and ignore
+        let contents = format!(
+            include_str!("./templates/search-index.js"),
+            searchIndexJson = format!("[{body}]"),
+        ) + &comment;
+        Ok(vec![(path.to_owned(), contents.into_bytes())])
+    }
+}
+
+/// The `search-index.js` file written by rustdoc 1.52 through 1.75.
+///
+/// ```js
+/// var searchIndex = JSON.parse('{"crate":{...},...}');
+/// ```
+pub struct JsonObject;
+
+impl SearchIndexFormat for JsonObject {
+    fn name(&self) -> &'static str {
+        "search-index.js (object)"
+    }
+
+    fn locate(&self, dir: &Path) -> Option<PathBuf> {
+        find_search_index_js(dir).filter(|path| {
+            fs::read_to_string(path).is_ok_and(|content| {
+                content.contains("JSON.parse(") && !content.contains("new Map(JSON.parse(")
+            })
+        })
+    }
+
+    fn read(&self, path: &Path) -> Result<SearchIndex> {
+        let json = js::read_json_parse_call(path)?;
+        if !json.is_object() {
+            return Err(MergeError::invalid(path, "search index must be a JSON object").into());
+        }
+        Ok(json
+            .entries()
+            .map(|(name, data)| (name.to_owned(), data.clone()))
+            .collect())
+    }
+
+    fn render(&self, path: &Path, index: &SearchIndex) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        let mut json = JsonValue::new_object();
+        for (name, data) in index {
+            json[name.as_str()] = data.clone();
+        }
+        let contents = format!(
+            include_str!("./templates/search-index-object.js"),
+            searchIndexJson = js::escape_string(&json.dump()),
+        );
+        Ok(vec![(path.to_owned(), contents.into_bytes())])
+    }
+}
+
+/// The `search.index/` directory written by rustdoc 1.94 through 1.98.
+///
+/// The files of this index are laid out by the stringdex crate, and read by the search script of
+/// the release of stringdex that rustdoc was built with, in `static.files/`. Each release that
+/// doc-merge can write is a format of its own. rustdoc 1.91 through 1.93 wrote the same directory
+/// with earlier releases, which doc-merge cannot write; their index is detected so that doc-merge
+/// can refuse to merge it with a useful message instead of silently producing a broken search.
+///
+/// See the [`stringdex`](crate::stringdex) module for how the index is split up by crate.
+pub enum Stringdex {
+    /// stringdex 0.0.5, used by rustdoc 1.94 and 1.95.
+    V0_0_5,
+
+    /// stringdex 0.0.6, used by rustdoc 1.96 through 1.98.
+    V0_0_6,
+}
+
+impl Stringdex {
+    /// The search script of the release in `static.files/`.
+    fn script(&self) -> &'static str {
+        match self {
+            Self::V0_0_5 => "stringdex-b897f86f.js",
+            Self::V0_0_6 => "stringdex-2da4960a.js",
+        }
+    }
+
+    /// The length of the hashes that name the files of the index.
+    fn hash_len(&self) -> usize {
+        match self {
+            Self::V0_0_5 => 12,
+            Self::V0_0_6 => 10,
+        }
+    }
+
+    /// The release, for reading and writing the index.
+    fn codec(&self) -> &'static dyn Codec {
+        match self {
+            Self::V0_0_5 => &Stringdex005,
+            Self::V0_0_6 => &Stringdex006,
+        }
+    }
+}
+
+impl SearchIndexFormat for Stringdex {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::V0_0_5 => "search.index/ (stringdex 0.0.5)",
+            Self::V0_0_6 => "search.index/ (stringdex 0.0.6)",
+        }
+    }
+
+    fn locate(&self, dir: &Path) -> Option<PathBuf> {
+        let root = stringdex::find_root(dir)?;
+        let hashed = dir
+            .join(stringdex::DIR)
+            .join("name")
+            .read_dir()
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .any(|entry| {
+                Path::new(&entry.file_name())
+                    .file_stem()
+                    .is_some_and(|stem| stem.len() == self.hash_len())
+            });
+        (hashed && dir.join("static.files").join(self.script()).is_file()).then_some(root)
+    }
+
+    fn read(&self, path: &Path) -> Result<SearchIndex> {
+        stringdex::read(self.codec(), path)
+    }
+
+    fn render(&self, path: &Path, index: &SearchIndex) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+        stringdex::render(self.codec(), path, index)
+    }
+
+    fn write(&self, path: &Path, files: &[(PathBuf, Vec<u8>)]) -> Result<()> {
+        // the files are named after their contents, so the ones of the old index are all stale
+        if let Some(dir) = path.parent().filter(|dir| dir.is_dir()) {
+            fs::remove_dir_all(dir).map_err(MergeError::io(dir))?;
+        }
+        for (file, contents) in files {
+            fsutil::write_file(file, contents)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether writing the search index of a documentation directory to `index_rel` (relative to the
+/// directory) replaces the file or directory at `rel`.
+///
+/// A `search.index/` directory is written anew every time, and the files of the other
+/// generations of the index, which a site documented again by a different toolchain still holds,
+/// would go stale. A `search.index/` directory left behind would even be found instead of a new
+/// `search-index.js`.
+pub(crate) fn replaces(index_rel: &Path, rel: &Path) -> bool {
+    let top_level = rel.parent() == Some(Path::new(""));
+    if rel.starts_with(stringdex::DIR) {
+        return true;
+    }
+    if index_rel.starts_with(stringdex::DIR) {
+        // the descriptions of the crates are kept next to the index from rustdoc 1.78 through 1.90
+        rel.starts_with("search.desc") || (top_level && is_search_index_js(rel))
+    } else {
+        top_level && rel != index_rel && is_search_index_js(rel)
+    }
+}
+
+/// Delete the files and directories of the documentation directory `dir` that writing its search
+/// index to `index_rel` [`replaces`].
+pub(crate) fn remove_replaced(dir: &Path, index_rel: &Path) -> Result<()> {
+    let Ok(entries) = dir.read_dir() else {
+        return Ok(());
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if !replaces(index_rel, Path::new(&entry.file_name())) {
+            continue;
+        }
+        if path.is_dir() {
+            fs::remove_dir_all(&path).map_err(MergeError::io(&path))?;
+        } else {
+            fs::remove_file(&path).map_err(MergeError::io(&path))?;
+        }
+    }
+    Ok(())
+}
+
+/// Find `search-index.js`, or `search-index<suffix>.js` when rustdoc ran with
+/// `--resource-suffix`.
+fn find_search_index_js(dir: &Path) -> Option<PathBuf> {
+    let path = dir.join("search-index.js");
+    if path.is_file() {
+        return Some(path);
+    }
+    dir.read_dir()
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| is_search_index_js(path))
+}
+
+/// Whether `path` is named like a `search-index.js`, with or without a resource suffix.
+fn is_search_index_js(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with("search-index") && name.ends_with(".js"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `search-index.js` as written by rustdoc 1.90 for two crates.
+    const SEARCH_INDEX_1_90: &str = r#"var searchIndex = new Map(JSON.parse('[["alpha",{"t":"FNNNNNNNCNNNN","n":["Thing","borrow","borrow_mut","clone","clone_into","clone_to_uninit","from","into","sub","to_owned","try_from","try_into","type_id"],"q":[[0,"alpha"],[13,"core::result"],[14,"core::any"]],"i":"`f000000`0000","f":"`{b{{b{c}}}{}}{{{b{d}}}{{b{dc}}}{}}{{{b{f}}}f}{{b{b{dc}}}h{}}{{bj}h}{cc{}}{{}c{}}`{bc{}}{c{{l{e}}}{}{}}{{}{{l{c}}}{}}{bn}","D":"j","p":[[1,"reference",null,null,1],[0,"mut"],[5,"Thing",0],[1,"unit"],[1,"u8"],[6,"Result",13,null,1],[5,"TypeId",14]],"r":[],"b":[],"c":"OjAAAAAAAAA=","e":"OzAAAAEAAAgAAgACAAQACgADAA==","P":[[1,"T"],[3,""],[4,"T"],[5,""],[6,"T"],[7,"U"],[9,"T"],[10,"U,T"],[11,"U"],[12,""]]}],["beta",{"t":"FNNNNNNNNNNN","n":["Other","borrow","borrow_mut","clone","clone_into","clone_to_uninit","from","into","to_owned","try_from","try_into","type_id"],"q":[[0,"beta"],[12,"core::result"],[13,"core::any"]],"i":"`f0000000000","f":"`{b{{b{c}}}{}}{{{b{d}}}{{b{dc}}}{}}{{{b{f}}}f}{{b{b{dc}}}h{}}{{bj}h}{cc{}}{{}c{}}{bc{}}{c{{l{e}}}{}{}}{{}{{l{c}}}{}}{bn}","D":"h","p":[[1,"reference",null,null,1],[0,"mut"],[5,"Other",0],[1,"unit"],[1,"u8"],[6,"Result",12,null,1],[5,"TypeId",13]],"r":[],"b":[],"c":"OjAAAAAAAAA=","e":"OzAAAAEAAAgAAgACAAQACQADAA==","P":[[1,"T"],[3,""],[4,"T"],[5,""],[6,"T"],[7,"U"],[8,"T"],[9,"U,T"],[10,"U"],[11,""]]}]]'));
+if (typeof exports !== 'undefined') exports.searchIndex = searchIndex;
+else if (window.initSearch) window.initSearch(searchIndex);
+//{"start":39,"fragment_lengths":[653,642]}"#;
+
+    #[test]
+    fn json_map_writes_the_fragments_like_rustdoc() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("search-index.js");
+        fs::write(&path, SEARCH_INDEX_1_90).unwrap();
+        let (format, found) = detect(dir.path()).unwrap();
+        assert_eq!(format.name(), JsonMap.name());
+        let index = format.read(&found).unwrap();
+        assert_eq!(index.keys().collect::<Vec<_>>(), ["alpha", "beta"]);
+        let files = format.render(&path, &index).unwrap();
+        assert_eq!(files, [(path, SEARCH_INDEX_1_90.as_bytes().to_vec())]);
+    }
+}
@@ -0,0 +1,244 @@
+//! Merging of the source browser's index, `src-files.js`.
+//!
+//! The index lists the source tree of every documented crate, so like the search index it has to
+//! be unioned across sources for the "Source" view to show every crate.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use jzon::JsonValue;
+use regex::Regex;
+
+use crate::{fsutil, js, MergeError, MergeReport};
+
+/// The names of the source index. Before rustdoc 1.76, it was called `source-files.js`.
+pub(crate) const FILE_NAMES: &[&str] = &["src-files.js", "source-files.js"];
+
+/// A parsed source index.
+#[derive(Debug)]
+pub(crate) struct SrcFiles {
+    /// The file name the index was read from.
+    file_name: &'static str,
+
+    /// Everything up to the opening quote of the JSON string.
+    head: String,
+
+    /// Whether the data is an array of `[crate, tree]` pairs rather than an object.
+    pairs: bool,
+
+    /// Everything after the closing quote of the JSON string, up to the fragment comment.
+    tail: String,
+
+    /// Whether the file ended with a `//{"start":..}` fragment comment.
+    fragments: bool,
+
+    /// The source tree of each crate.
+    pub(crate) crates: BTreeMap<String, JsonValue>,
+}
+
+impl SrcFiles {
+    /// Find and parse the source index in a documentation directory, if it has one.
+    pub(crate) fn find(dir: &Path) -> Result<Option<Self>> {
+        for file_name in FILE_NAMES {
+            let path = dir.join(file_name);
+            if path.is_file() {
+                return Self::read(&path, file_name).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Parse the source index at `path`.
+    fn read(path: &Path, file_name: &'static str) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(MergeError::io(path))?;
+        let invalid = || MergeError::invalid(path, "not a rustdoc source index");
+
+        let captures = Regex::new(
+            r"(?s)^(.*?(?:JSON\.parse|createSrcSidebar|createSourceSidebar)\(')((?:[^'\\]|\\.)*)'",
+        )?
+        .captures(&content)
+        .ok_or_else(invalid)?;
+        let (head, data) = (&captures[1], &captures[2]);
+        let tail_start = captures.get(0).expect("whole match").end();
+        let (tail_end, fragments) = match content.rfind("//{\"start\"") {
+            Some(i) if i > tail_start => (i, true),
+            _ => (content.len(), false),
+        };
+
+        let json = jzon::parse(&js::unescape_string(data))
+            .map_err(|err| MergeError::invalid(path, format!("invalid JSON: {err}")))?;
+        let pairs = json.is_array();
+        let crates = if pairs {
+            json.members()
+                .map(|item| {
+                    let name = item[0].as_str().ok_or_else(invalid)?;
+                    Ok((name.to_owned(), item[1].clone()))
+                })
+                .collect::<Result<_>>()?
+        } else {
+            json.entries()
+                .map(|(name, data)| (name.to_owned(), data.clone()))
+                .collect()
+        };
+
+        Ok(Self {
+            file_name,
+            head: head.to_owned(),
+            pairs,
+            tail: content[tail_start..tail_end].to_owned(),
+            fragments,
+            crates,
+        })
+    }
+
+    /// Write the source index into the documentation directory `dir`.
+    pub(crate) fn write(&self, dir: &Path) -> Result<()> {
+        let fragments = self
+            .crates
+            .iter()
+            .map(|(name, data)| {
+                js::escape_string(&if self.pairs {
+                    jzon::array![name.as_str(), data.clone()].dump()
+                } else {
+                    format!("{}:{}", JsonValue::from(name.as_str()).dump(), data.dump())
+                })
+            })
+            .collect::<Vec<_>>();
+        let (open, close) = if self.pairs { ("[", "]") } else { ("{", "}") };
+        let (body, comment) = js::join_fragments(self.head.len() + open.len(), &fragments);
+
+        let mut out = format!("{}{open}{body}{close}'{}", self.head, self.tail);
+        if self.fragments {
+            out.push_str(&comment);
+        }
+        fsutil::write_file(&dir.join(self.file_name), out)
+    }
+}
+
+/// The name of the source index [`merge`] writes: that of the first source that has one.
+pub(crate) fn file_name(sources: &[PathBuf]) -> Option<&'static str> {
+    sources.iter().find_map(|src| {
+        FILE_NAMES
+            .iter()
+            .find(|file_name| src.join(file_name).is_file())
+            .copied()
+    })
+}
+
+/// Merge the source index of every source into `dest`.
+///
+/// Each crate's source tree is taken from the same source its search index entry was taken from,
+/// and those of the crates left out of the merge are dropped.
+pub(crate) fn merge(sources: &[PathBuf], report: &MergeReport, dest: &Path) -> Result<()> {
+    let mut merged: Option<SrcFiles> = None;
+    for src in sources {
+        let Some(mut src_files) = SrcFiles::find(src)? else {
+            continue;
+        };
+        src_files.crates.retain(|name, _| report.takes(name, src));
+        match &mut merged {
+            Some(merged) => merged.crates.extend(src_files.crates),
+            None => merged = Some(src_files),
+        }
+    }
+    match merged {
+        Some(merged) => merged.write(dest),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `src-files.js` as written by rustdoc 1.95, for two crates.
+    const SRC_FILES_1_95: &str = r#"createSrcSidebar('[["alpha",["",[["sub",[],["mod.rs"]]],["lib.rs"]]],["beta",["",[],["lib.rs"]]]]');
+//{"start":19,"fragment_lengths":[49,28]}"#;
+
+    /// `src-files.js` as written by rustdoc 1.90.
+    const SRC_FILES_1_90: &str = r#"createSrcSidebar('[["beta",["",[],["lib.rs"]]]]');
+//{"start":19,"fragment_lengths":[27]}"#;
+
+    /// `src-files.js` as written by rustdoc 1.76, with the data broken over several lines.
+    const SRC_FILES_1_76: &str = "var srcIndex = new Map(JSON.parse('[\\\n[\"gamma\",[\"\",[],[\"lib.rs\"]]]\\\n]'));\ncreateSrcSidebar();\n";
+
+    /// `source-files.js` as written before rustdoc 1.76, with an object.
+    const SOURCE_FILES: &str =
+        "var sourcesIndex = JSON.parse('{\\\n\"c\":[\"\",[],[\"lib.rs\"]]\\\n}');\ncreateSourceSidebar();\n";
+
+    /// Read `content` as the source index called `file_name` in `dir`.
+    fn read(dir: &Path, file_name: &'static str, content: &str) -> SrcFiles {
+        fs::write(dir.join(file_name), content).unwrap();
+        SrcFiles::find(dir).unwrap().unwrap()
+    }
+
+    /// Write `src_files` into `dir` and return what was written.
+    fn write(dir: &Path, src_files: &SrcFiles) -> String {
+        src_files.write(dir).unwrap();
+        fs::read_to_string(dir.join(src_files.file_name)).unwrap()
+    }
+
+    #[test]
+    fn round_trips_the_fragment_form() {
+        for content in [SRC_FILES_1_95, SRC_FILES_1_90] {
+            let dir = tempfile::tempdir().unwrap();
+            let src_files = read(dir.path(), "src-files.js", content);
+            assert!(src_files.pairs && src_files.fragments);
+            assert_eq!(write(dir.path(), &src_files), content);
+        }
+    }
+
+    #[test]
+    fn reads_the_tree_of_each_crate() {
+        let dir = tempfile::tempdir().unwrap();
+        let src_files = read(dir.path(), "src-files.js", SRC_FILES_1_95);
+        assert_eq!(
+            src_files.crates.keys().collect::<Vec<_>>(),
+            ["alpha", "beta"]
+        );
+        assert_eq!(src_files.crates["alpha"][1][0][0], "sub");
+        assert_eq!(src_files.crates["beta"][2][0], "lib.rs");
+    }
+
+    #[test]
+    fn writes_older_forms_on_one_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let src_files = read(dir.path(), "src-files.js", SRC_FILES_1_76);
+        assert!(src_files.pairs && !src_files.fragments);
+        assert_eq!(
+            write(dir.path(), &src_files),
+            "var srcIndex = new Map(JSON.parse('[[\"gamma\",[\"\",[],[\"lib.rs\"]]]]'));\n\
+             createSrcSidebar();\n"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let src_files = read(dir.path(), "source-files.js", SOURCE_FILES);
+        assert!(!src_files.pairs && !src_files.fragments);
+        assert_eq!(
+            write(dir.path(), &src_files),
+            "var sourcesIndex = JSON.parse('{\"c\":[\"\",[],[\"lib.rs\"]]}');\n\
+             createSourceSidebar();\n"
+        );
+    }
+
+    #[test]
+    fn escapes_merged_crates() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut src_files = read(dir.path(), "src-files.js", SRC_FILES_1_90);
+        src_files
+            .crates
+            .insert("alpha".to_owned(), jzon::array!["", [], ["it's.rs"]]);
+        let written = write(dir.path(), &src_files);
+        assert_eq!(
+            written,
+            r#"createSrcSidebar('[["alpha",["",[],["it\'s.rs"]]],["beta",["",[],["lib.rs"]]]]');
+//{"start":19,"fragment_lengths":[30,28]}"#
+        );
+        assert_eq!(
+            read(dir.path(), "src-files.js", &written).crates,
+            src_files.crates
+        );
+    }
+}
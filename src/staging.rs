@@ -0,0 +1,184 @@
+//! Building the merged site next to the destination, and swapping it into place once the merge
+//! has succeeded.
+//!
+//! A merge that fails half-way must not leave a half-written site behind for the web server to
+//! serve, so the site is built in a staging directory beside the destination. The staging
+//! directory starts out as a copy of the destination made of hard links, so that adding to a
+//! large site stays cheap; every write replaces files instead of writing to them in place (see
+//! [`fsutil::write_file`]), which leaves the live site untouched.
+//!
+//! On Linux, the staged site and the live one trade places in a single rename, so the destination
+//! never goes missing; elsewhere, there is a moment between moving the live site out of the way and
+//! moving the staged one in.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::{fsutil, MergeError, Unchanged};
+
+/// A staging directory for the site, or the directory inside a site, at `dest`.
+#[derive(Debug)]
+pub(crate) struct Staging {
+    dest: PathBuf,
+    path: PathBuf,
+    previous: PathBuf,
+}
+
+impl Staging {
+    /// Create the staging directory for `dest`, holding the same files as `dest`.
+    ///
+    /// `site` is the root of the published site that `dest` is, or is a directory of, such as
+    /// one version of a site with several. The staging directory and the previous generation are
+    /// kept next to it rather than inside it, where the web server would publish them.
+    ///
+    /// A staging directory left behind by an earlier merge that failed is cleared out first.
+    pub(crate) fn create(dest: &Path, site: &Path) -> Result<Self> {
+        let staging = Self::empty(dest, site)?;
+        let (dest, path) = (&staging.dest, &staging.path);
+        if dest.is_dir() {
+            for rel in fsutil::walk_files(dest)? {
+                let target = path.join(&rel);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent).map_err(MergeError::io(parent))?;
+                }
+                // hard links do not work across filesystems, so fall back to copying
+                if fs::hard_link(dest.join(&rel), &target).is_err() {
+                    fsutil::copy_file(&dest.join(&rel), &target)?;
+                }
+            }
+        }
+        Ok(staging)
+    }
+
+    /// Create an empty staging directory for `dest` inside `site`, for replacing everything in
+    /// it.
+    pub(crate) fn empty(dest: &Path, site: &Path) -> Result<Self> {
+        let dest = std::path::absolute(dest).map_err(MergeError::io(dest))?;
+        let site = std::path::absolute(site).map_err(MergeError::io(site))?;
+        let path = sibling(&dest, &site, "staging")?;
+        let previous = sibling(&dest, &site, "previous")?;
+        if path.exists() {
+            fs::remove_dir_all(&path).map_err(MergeError::io(&path))?;
+        }
+        fs::create_dir_all(&path).map_err(MergeError::io(&path))?;
+        Ok(Self {
+            dest,
+            path,
+            previous,
+        })
+    }
+
+    /// The staging directory.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Put the live site's file back in place of each of the staged `files` (relative to the
+    /// site) that has the same contents, so that it keeps its modification time. Returns how
+    /// many files were put back.
+    pub(crate) fn keep_unchanged<'a>(
+        &self,
+        files: impl IntoIterator<Item = &'a PathBuf>,
+    ) -> Result<usize> {
+        let mut kept = 0;
+        for rel in files {
+            let (live, staged) = (self.dest.join(rel), self.path.join(rel));
+            if !live.is_file() || !Unchanged::Content.is_unchanged(&live, &staged)? {
+                continue;
+            }
+            fs::remove_file(&staged).map_err(MergeError::io(&staged))?;
+            if fs::hard_link(&live, &staged).is_err() {
+                fsutil::copy_file(&live, &staged)?;
+            }
+            kept += 1;
+        }
+        Ok(kept)
+    }
+
+    /// Move the staged site into place.
+    ///
+    /// The site that was there before is kept as the previous generation, so that it can be
+    /// restored by hand; the one before that is deleted. Returns where the previous generation
+    /// was moved to, if there was one.
+    pub(crate) fn commit(self) -> Result<Option<PathBuf>> {
+        if !self.dest.exists() && !self.dest.is_symlink() {
+            // a version is staged outside its site, which may not exist yet either
+            if let Some(parent) = self.dest.parent() {
+                fs::create_dir_all(parent).map_err(MergeError::io(parent))?;
+            }
+            fs::rename(&self.path, &self.dest).map_err(MergeError::io(&self.dest))?;
+            return Ok(None);
+        }
+        let previous = self.previous;
+        if previous.exists() || previous.is_symlink() {
+            fs::remove_dir_all(&previous).map_err(MergeError::io(&previous))?;
+        }
+        if exchange(&self.path, &self.dest).is_ok() {
+            // the live site is now where the staged one was
+            fs::rename(&self.path, &previous).map_err(MergeError::io(&self.path))?;
+            return Ok(Some(previous));
+        }
+        // not supported here, or not by this filesystem: fall back to two renames
+        fs::rename(&self.dest, &previous).map_err(MergeError::io(&self.dest))?;
+        if let Err(err) = fs::rename(&self.path, &self.dest) {
+            // put the live site back rather than leaving nothing behind
+            fs::rename(&previous, &self.dest).map_err(MergeError::io(&previous))?;
+            return Err(MergeError::io(&self.path)(err).into());
+        }
+        Ok(Some(previous))
+    }
+
+    /// Delete the staging directory, after a merge that failed.
+    pub(crate) fn discard(self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Swap the files or directories at `a` and `b` in a single step.
+#[cfg(target_os = "linux")]
+fn exchange(a: &Path, b: &Path) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let a = CString::new(a.as_os_str().as_bytes())?;
+    let b = CString::new(b.as_os_str().as_bytes())?;
+    // SAFETY: both paths are NUL-terminated strings that outlive the call
+    let result = unsafe {
+        libc::renameat2(
+            libc::AT_FDCWD,
+            a.as_ptr(),
+            libc::AT_FDCWD,
+            b.as_ptr(),
+            libc::RENAME_EXCHANGE,
+        )
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Swap the files or directories at `a` and `b` in a single step.
+#[cfg(not(target_os = "linux"))]
+fn exchange(_a: &Path, _b: &Path) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+/// The hidden directory next to `site` for `dest`: `.<site>.<suffix>` for the site itself, and
+/// `.<site>.<dir>.<suffix>` for the directory `<dir>` inside it.
+fn sibling(dest: &Path, site: &Path, suffix: &str) -> Result<PathBuf> {
+    let name = site
+        .file_name()
+        .with_context(|| format!("{} has no directory name", site.display()))?;
+    let mut name = format!(".{}", name.to_string_lossy());
+    if let Ok(rel) = dest.strip_prefix(site) {
+        for part in rel {
+            name = format!("{name}.{}", part.to_string_lossy());
+        }
+    }
+    Ok(site.with_file_name(format!("{name}.{suffix}")))
+}
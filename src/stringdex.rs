@@ -0,0 +1,1212 @@
+//! Reading and writing the `search.index/` directory that rustdoc keeps its search index in since
+//! 1.91. doc-merge reads and writes the directory of rustdoc 1.94 through 1.98, whose releases
+//! of stringdex it is built with.
+//!
+//! Instead of a list of items for each crate, this index is a single table for the whole site,
+//! stored column by column with the stringdex crate: `search.index/root.js` describes every
+//! column, and the cells themselves are spread over content-addressed files below
+//! `search.index/<column>/`. Rows refer to each other by number, and a path that several crates
+//! mention, such as `core::option::Option`, has one row that all of them share. A suffix tree of
+//! every name, for finding items as the reader types, is laid out in the files directly in
+//! `search.index/`.
+//!
+//! doc-merge splits the table into a part for each crate, numbered on its own, and joins the
+//! parts back into one table the way rustdoc does when it documents crates into the same
+//! directory, before building the tree again.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::convert::Infallible;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use jzon::JsonValue;
+use stringdex_0_0_6::internals::{decode, encode};
+
+use crate::search_index::SearchIndex;
+use crate::MergeError;
+
+/// The directory rustdoc writes the index to.
+pub(crate) const DIR: &str = "search.index";
+
+/// The columns whose cells are JSON, in the order rustdoc lays them out.
+const JSON_COLUMNS: [&str; 5] = ["path", "entry", "function", "type", "alias"];
+
+/// A release of stringdex, which lays out the files of the index.
+///
+/// Each version of rustdoc reads its index with the search script of the release it was built
+/// with, so the index has to be written by the same release it was read with.
+pub(crate) trait Codec: Sync {
+    /// Read every cell of `column`, as described by the `root` file, loading its files with
+    /// `load`.
+    fn read_column(
+        &self,
+        root: &[u8],
+        column: &str,
+        load: &mut dyn FnMut(&str, &mut Vec<u8>) -> io::Result<()>,
+    ) -> Result<Vec<Vec<u8>>, String>;
+
+    /// Lay out `cells` as the files of a column, returning its description for the root file.
+    fn write_column(&self, cells: &[Vec<u8>], files: &mut Files) -> io::Result<Vec<u8>>;
+
+    /// Lay out the suffix tree of `names`, returning its root node.
+    fn write_tree(&self, names: &[String], files: &mut Files) -> io::Result<Vec<u8>>;
+
+    /// Read a bitmap of row numbers, returning the rows and the number of bytes it took up.
+    fn read_bitmap(&self, bytes: &[u8]) -> Option<(Vec<u32>, usize)>;
+
+    /// Write a bitmap of the sorted row numbers `rows`.
+    fn write_bitmap(&self, rows: &[u32], out: &mut Vec<u8>);
+}
+
+/// The files laid out by a [`Codec`], by name.
+#[derive(Debug, Default)]
+pub(crate) struct Files(BTreeMap<String, Vec<u8>>);
+
+/// Implement [`Codec`] with one release of stringdex.
+macro_rules! codec {
+    ($codec:ident, $stringdex:ident) => {
+        pub(crate) struct $codec;
+
+        impl Codec for $codec {
+            fn read_column(
+                &self,
+                root: &[u8],
+                column: &str,
+                load: &mut dyn FnMut(&str, &mut Vec<u8>) -> io::Result<()>,
+            ) -> Result<Vec<Vec<u8>>, String> {
+                let mut cells = Vec::new();
+                $stringdex::internals::read_data_from_column(
+                    root,
+                    column.as_bytes(),
+                    &mut |name: &str, buf: &mut Vec<u8>| load(name, buf),
+                    &mut |_, cell: &[u8]| {
+                        cells.push(cell.to_vec());
+                        Ok::<_, Infallible>(())
+                    },
+                )
+                .map_err(|err| err.to_string())?;
+                Ok(cells)
+            }
+
+            fn write_column(&self, cells: &[Vec<u8>], files: &mut Files) -> io::Result<Vec<u8>> {
+                $stringdex::internals::write_data(cells.iter(), files)
+            }
+
+            fn write_tree(&self, names: &[String], files: &mut Files) -> io::Result<Vec<u8>> {
+                let tree = $stringdex::internals::tree::encode_search_tree_ukkonen(
+                    names.iter().map(String::as_bytes),
+                );
+                $stringdex::internals::write_tree(&tree, files)
+            }
+
+            fn read_bitmap(&self, bytes: &[u8]) -> Option<(Vec<u32>, usize)> {
+                $stringdex::internals::decode::RoaringBitmap::from_bytes(bytes)
+                    .map(|(bitmap, len)| (bitmap.to_vec(), len))
+            }
+
+            fn write_bitmap(&self, rows: &[u32], out: &mut Vec<u8>) {
+                $stringdex::internals::encode::write_bitmap_to_bytes(rows, out)
+                    .expect("writing to memory cannot fail");
+            }
+        }
+
+        impl $stringdex::internals::fs::Filesystem for Files {
+            fn read_file(&mut self, name: &str) -> io::Result<Option<Vec<u8>>> {
+                Ok(self.0.get(name).cloned())
+            }
+
+            fn write_file(&mut self, name: &str, contents: &[u8]) -> io::Result<()> {
+                self.0.insert(name.to_owned(), contents.to_vec());
+                Ok(())
+            }
+        }
+    };
+}
+
+codec!(Stringdex005, stringdex_0_0_5);
+codec!(Stringdex006, stringdex_0_0_6);
+
+/// Find the root file of the index in a documentation directory: `search.index/root.js`, or
+/// `search.index/root<suffix>.js` when rustdoc ran with `--resource-suffix`.
+pub(crate) fn find_root(dir: &Path) -> Option<PathBuf> {
+    dir.join(DIR)
+        .read_dir()
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("root") && name.ends_with(".js"))
+        })
+}
+
+/// Read the index whose root file is at `root`, split into the part of each crate.
+pub(crate) fn read(codec: &dyn Codec, root: &Path) -> Result<SearchIndex> {
+    let table = Table::read(codec, root)?;
+    Ok(table
+        .split()
+        .into_iter()
+        .map(|(crate_name, part)| (crate_name, part.to_json()))
+        .collect())
+}
+
+/// Lay out the index made of the parts in `index` as the files of an index whose root file is at
+/// `root`, with their contents.
+pub(crate) fn render(
+    codec: &dyn Codec,
+    root: &Path,
+    index: &SearchIndex,
+) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    let parts = index
+        .iter()
+        .map(|(crate_name, data)| {
+            Table::from_json(data).ok_or_else(|| MergeError::InvalidFile {
+                path: root.to_owned(),
+                crate_name: Some(crate_name.clone()),
+                reason: "the search index data of this crate was not read from a search.index/ \
+                         directory"
+                    .to_owned(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let dir = root.parent().unwrap_or(Path::new(""));
+    Table::join(parts)
+        .sorted()
+        .render(codec, root)
+        .map_err(|err| MergeError::io(dir)(err).into())
+}
+
+/// Lists of function rows, grouped by the number of types in their signature.
+type Postings = Vec<Vec<u32>>;
+
+/// The functions whose signature mentions a type.
+#[derive(Debug, Clone, Default)]
+struct TypeData {
+    inputs: Postings,
+    outputs: Postings,
+
+    /// Whether the type can be left out of a query for its generics, like `Option` or `Vec`.
+    unbox: bool,
+}
+
+/// An index, column by column. Cells that rustdoc leaves empty are `null` or `None`.
+///
+/// The paths, entries and functions are kept as rustdoc writes them:
+///
+/// - a path is `[item type, "module::path"]`, with the exact module path after it for some
+/// - an entry is `[crate, item type, module, exact module, parent, trait parent, deprecated,
+///   unstable]`, with a disambiguator after it for some. The crate is a row, and the module and
+///   parents are rows plus one, or 0 for none.
+/// - a function is `[signature, [parameter names]]`. The signature lists the types of the
+///   parameters, results and where clauses as rows plus one, or as negative numbers for generics.
+#[derive(Debug, Default)]
+struct Table {
+    names: Vec<String>,
+    paths: Vec<JsonValue>,
+    entries: Vec<JsonValue>,
+    descs: Vec<String>,
+    functions: Vec<JsonValue>,
+    types: Vec<Option<TypeData>>,
+    aliases: Vec<Option<usize>>,
+
+    /// The functions mentioning each generic, by how many generics come before it.
+    generics: Vec<Postings>,
+}
+
+impl Table {
+    /// Read the table whose root file is at `root`.
+    fn read(codec: &dyn Codec, root: &Path) -> Result<Self> {
+        let root_file = fs::read(root).map_err(MergeError::io(root))?;
+        let dir = root.parent().unwrap_or(Path::new(""));
+        let invalid = |reason: String| MergeError::invalid(root, reason);
+        let column = |name: &str| -> Result<Vec<Vec<u8>>> {
+            let mut failed = None;
+            let mut load = |file: &str, buf: &mut Vec<u8>| {
+                let path = dir.join(name).join(format!("{file}.js"));
+                match fs::read(&path) {
+                    Ok(contents) => {
+                        buf.extend(contents);
+                        Ok(())
+                    }
+                    Err(err) => {
+                        let kind = err.kind();
+                        failed = Some(MergeError::io(&path)(err));
+                        Err(kind.into())
+                    }
+                }
+            };
+            let cells = codec.read_column(&root_file, name, &mut load);
+            match (cells, failed) {
+                (Ok(cells), _) => Ok(cells),
+                (Err(_), Some(err)) => Err(err.into()),
+                (Err(err), None) => Err(invalid(format!("column `{name}`: {err}")).into()),
+            }
+        };
+        let strings = |name: &str| -> Result<Vec<String>> {
+            column(name)?
+                .into_iter()
+                .map(|cell| {
+                    String::from_utf8(cell)
+                        .map_err(|_| invalid(format!("column `{name}` is not valid UTF-8")).into())
+                })
+                .collect()
+        };
+        let mut json = BTreeMap::new();
+        for name in JSON_COLUMNS {
+            let cells = strings(name)?
+                .iter()
+                .map(|cell| match cell.as_str() {
+                    "" => Ok(JsonValue::Null),
+                    cell => jzon::parse(cell)
+                        .map_err(|err| invalid(format!("column `{name}`: invalid JSON: {err}"))),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            json.insert(name, cells);
+        }
+        let mut json = |name| json.remove(name).unwrap_or_default();
+
+        let table = Self {
+            names: strings("name")?,
+            paths: json("path"),
+            entries: json("entry"),
+            descs: strings("desc")?,
+            functions: json("function"),
+            types: json("type")
+                .iter()
+                .map(|cell| match cell {
+                    JsonValue::Null => Ok(None),
+                    cell => TypeData::read(codec, cell)
+                        .map(Some)
+                        .ok_or_else(|| invalid("column `type`: invalid postings".to_owned())),
+                })
+                .collect::<Result<_, _>>()?,
+            aliases: json("alias").iter().map(JsonValue::as_usize).collect(),
+            generics: column("generic_inverted_index")?
+                .iter()
+                .map(|cell| read_postings(codec, cell))
+                .collect::<Option<_>>()
+                .ok_or_else(|| {
+                    invalid("column `generic_inverted_index`: invalid postings".to_owned())
+                })?,
+        };
+        let len = table.names.len();
+        if [
+            table.paths.len(),
+            table.entries.len(),
+            table.descs.len(),
+            table.functions.len(),
+            table.types.len(),
+            table.aliases.len(),
+        ]
+        .iter()
+        .any(|&column_len| column_len != len)
+        {
+            return Err(invalid("the columns have different numbers of rows".to_owned()).into());
+        }
+
+        // rows refer to each other by number, and splitting the table follows the references
+        for row in 0..len {
+            let target = entry_refs(&table.entries[row])
+                .into_iter()
+                .chain(function_refs(&table.functions[row]))
+                .chain(table.aliases[row])
+                .find(|&target| target >= len);
+            if let Some(target) = target {
+                return Err(invalid(format!(
+                    "row {row} refers to row {target}, but there are only {len} rows"
+                ))
+                .into());
+            }
+        }
+        let functions = table
+            .types
+            .iter()
+            .flatten()
+            .flat_map(|types| types.inputs.iter().chain(&types.outputs))
+            .chain(table.generics.iter().flatten());
+        if functions.flatten().any(|&row| row as usize >= len) {
+            return Err(invalid(format!(
+                "the postings refer to functions past the {len} rows of the index"
+            ))
+            .into());
+        }
+        Ok(table)
+    }
+
+    /// The crate whose item the row is, if it is an item rather than only a path.
+    fn crate_of(&self, row: usize) -> Option<&str> {
+        let krate = self.entries[row][0].as_usize()?;
+        self.names.get(krate).map(String::as_str)
+    }
+
+    /// The item type and full path of a row with a path, which rows with the same path share.
+    fn path_key(&self, row: usize) -> Option<(u32, String)> {
+        let path = &self.paths[row];
+        let ty = path[0].as_u32()?;
+        let name = &self.names[row];
+        Some(match path[1].as_str()? {
+            "" => (ty, name.clone()),
+            module => (ty, format!("{module}::{name}")),
+        })
+    }
+
+    /// Split the table into the part of each crate, by crate name.
+    ///
+    /// Each part holds the crate's items and their aliases, and the paths that they refer to,
+    /// which may belong to other crates.
+    fn split(&self) -> BTreeMap<String, Table> {
+        let mut owned = BTreeMap::<&str, BTreeSet<usize>>::new();
+        for row in 0..self.names.len() {
+            if let Some(crate_name) = self.crate_of(row) {
+                owned.entry(crate_name).or_default().insert(row);
+            }
+        }
+        for (row, alias) in self.aliases.iter().enumerate() {
+            if let Some(crate_name) = alias.and_then(|target| self.crate_of(target)) {
+                owned.entry(crate_name).or_default().insert(row);
+            }
+        }
+        owned
+            .into_iter()
+            .map(|(crate_name, rows)| (crate_name.to_owned(), self.part(&rows)))
+            .collect()
+    }
+
+    /// The part of the table that holds the rows in `owned`, with the paths they refer to and the
+    /// types their functions take or return.
+    fn part(&self, owned: &BTreeSet<usize>) -> Table {
+        let mut rows = owned.clone();
+        for &row in owned {
+            rows.extend(entry_refs(&self.entries[row]));
+            rows.extend(function_refs(&self.functions[row]));
+        }
+        for (row, types) in self.types.iter().enumerate() {
+            if types.as_ref().is_some_and(|types| types.mentions(owned)) {
+                rows.insert(row);
+            }
+        }
+        let local = rows
+            .iter()
+            .enumerate()
+            .map(|(local, &row)| (row, local))
+            .collect::<HashMap<_, _>>();
+        let map = |row: usize| local.get(&row).copied();
+        let map_function = |row: u32| {
+            let row = usize::try_from(row).ok()?;
+            owned
+                .contains(&row)
+                .then(|| map(row))
+                .flatten()?
+                .try_into()
+                .ok()
+        };
+
+        let mut part = Table::default();
+        for &row in &rows {
+            part.names.push(self.names[row].clone());
+            part.paths.push(self.paths[row].clone());
+            part.types.push(
+                self.types[row]
+                    .as_ref()
+                    .map(|types| types.remap(map_function)),
+            );
+            if owned.contains(&row) {
+                part.entries.push(remap_entry(&self.entries[row], map));
+                part.descs.push(self.descs[row].clone());
+                part.functions
+                    .push(remap_function(&self.functions[row], map));
+                part.aliases.push(self.aliases[row].and_then(map));
+            } else {
+                part.entries.push(JsonValue::Null);
+                part.descs.push(String::new());
+                part.functions.push(JsonValue::Null);
+                part.aliases.push(None);
+            }
+        }
+        part.generics = self
+            .generics
+            .iter()
+            .map(|postings| remap_postings(postings, map_function))
+            .collect();
+        part
+    }
+
+    /// Join the `parts` of several crates into one table.
+    ///
+    /// A path that is in more than one part gets a single row, which takes the item, type data
+    /// and description from whichever part has them.
+    fn join(parts: impl IntoIterator<Item = Table>) -> Table {
+        let mut table = Table::default();
+        let mut by_path = HashMap::new();
+        for part in parts {
+            let rows = (0..part.names.len())
+                .map(|row| {
+                    let key = part.path_key(row);
+                    if let Some(&existing) = key.as_ref().and_then(|key| by_path.get(key)) {
+                        return existing;
+                    }
+                    let new = table.names.len();
+                    table.names.push(part.names[row].clone());
+                    table.paths.push(part.paths[row].clone());
+                    table.entries.push(JsonValue::Null);
+                    table.descs.push(String::new());
+                    table.functions.push(JsonValue::Null);
+                    table.types.push(None);
+                    table.aliases.push(None);
+                    if let Some(key) = key {
+                        by_path.insert(key, new);
+                    }
+                    new
+                })
+                .collect::<Vec<_>>();
+            let map = |row: usize| rows.get(row).copied();
+            let map_function =
+                |row: u32| map(usize::try_from(row).ok()?).and_then(|row| u32::try_from(row).ok());
+            for (row, &new) in rows.iter().enumerate() {
+                if table.entries[new].is_null() && !part.entries[row].is_null() {
+                    table.entries[new] = remap_entry(&part.entries[row], map);
+                    table.descs[new].clone_from(&part.descs[row]);
+                }
+                if table.functions[new].is_null() {
+                    table.functions[new] = remap_function(&part.functions[row], map);
+                }
+                if table.aliases[new].is_none() {
+                    table.aliases[new] = part.aliases[row].and_then(map);
+                }
+                if let Some(types) = &part.types[row] {
+                    let types = types.remap(map_function);
+                    match &mut table.types[new] {
+                        Some(existing) => existing.extend(types),
+                        slot => *slot = Some(types),
+                    }
+                }
+            }
+            for (position, postings) in part.generics.iter().enumerate() {
+                if table.generics.len() <= position {
+                    table.generics.resize(position + 1, Vec::new());
+                }
+                extend_postings(
+                    &mut table.generics[position],
+                    remap_postings(postings, map_function),
+                );
+            }
+        }
+        table
+    }
+
+    /// Put the rows in the order rustdoc keeps them in: shorter names first, which is the order
+    /// the search shows them in, then by name, crate and module.
+    fn sorted(self) -> Table {
+        let mut order = (0..self.names.len()).collect::<Vec<_>>();
+        order.sort_by_cached_key(|&row| {
+            let module = self.paths[row][1].as_str().unwrap_or_default();
+            (
+                self.names[row].len(),
+                self.names[row].clone(),
+                self.crate_of(row).unwrap_or_default().to_owned(),
+                module.split("::").map(str::to_owned).collect::<Vec<_>>(),
+            )
+        });
+        let mut position = vec![0; order.len()];
+        for (new, &row) in order.iter().enumerate() {
+            position[row] = new;
+        }
+        let map = |row: usize| position.get(row).copied();
+        let map_function =
+            |row: u32| map(usize::try_from(row).ok()?).and_then(|row| u32::try_from(row).ok());
+
+        let mut sorted = Table::default();
+        for &row in &order {
+            sorted.names.push(self.names[row].clone());
+            sorted.paths.push(self.paths[row].clone());
+            sorted.entries.push(remap_entry(&self.entries[row], map));
+            sorted.descs.push(self.descs[row].clone());
+            sorted
+                .functions
+                .push(remap_function(&self.functions[row], map));
+            sorted.types.push(
+                self.types[row]
+                    .as_ref()
+                    .map(|types| types.remap(map_function)),
+            );
+            sorted.aliases.push(self.aliases[row].and_then(map));
+        }
+        sorted.generics = self
+            .generics
+            .iter()
+            .map(|postings| remap_postings(postings, map_function))
+            .collect();
+        sorted
+    }
+
+    /// Lay out the table as the files of an index whose root file is at `root`.
+    fn render(&self, codec: &dyn Codec, root: &Path) -> io::Result<Vec<(PathBuf, Vec<u8>)>> {
+        let dir = root.parent().unwrap_or(Path::new(""));
+        let mut rendered = Vec::new();
+        let mut column = |name: &str, cells: Vec<Vec<u8>>| -> io::Result<Vec<u8>> {
+            let mut files = Files::default();
+            let description = codec.write_column(&cells, &mut files)?;
+            for (file, contents) in files.0 {
+                rendered.push((dir.join(name).join(format!("{file}.js")), contents));
+            }
+            Ok(description)
+        };
+        let json = |cells: &[JsonValue]| {
+            cells
+                .iter()
+                .map(|cell| match cell {
+                    JsonValue::Null => Vec::new(),
+                    cell => cell.dump().into_bytes(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // the names are searched for without case or underscores
+        let normalized = self
+            .names
+            .iter()
+            .map(|name| name.replace('_', "").to_ascii_lowercase())
+            .collect::<Vec<_>>();
+        let mut crates = (0..self.names.len())
+            .filter_map(|row| self.crate_of(row))
+            .collect::<Vec<_>>();
+        crates.sort();
+        crates.dedup();
+
+        let mut out = Vec::new();
+        out.extend_from_slice(br#"rr_('{"normalizedName":{"I":""#);
+        let mut tree = Files::default();
+        let tree_root = codec.write_tree(&normalized, &mut tree)?;
+        encode::write_base64_to_bytes(&tree_root, &mut out)?;
+        out.extend_from_slice(br#"","#);
+        out.extend(column(
+            "normalizedName",
+            normalized.into_iter().map(String::into_bytes).collect(),
+        )?);
+        out.extend_from_slice(br#"},"crateNames":{"#);
+        out.extend(column(
+            "crateNames",
+            crates
+                .into_iter()
+                .map(|name| name.as_bytes().to_vec())
+                .collect(),
+        )?);
+        out.extend_from_slice(br#"},"name":{"#);
+        out.extend(column(
+            "name",
+            self.names
+                .iter()
+                .map(|name| name.as_bytes().to_vec())
+                .collect(),
+        )?);
+        out.extend_from_slice(br#"},"path":{"#);
+        out.extend(column("path", json(&self.paths))?);
+        out.extend_from_slice(br#"},"entry":{"#);
+        out.extend(column("entry", json(&self.entries))?);
+        out.extend_from_slice(br#"},"desc":{"#);
+        out.extend(column(
+            "desc",
+            self.descs
+                .iter()
+                .map(|desc| desc.as_bytes().to_vec())
+                .collect(),
+        )?);
+        out.extend_from_slice(br#"},"function":{"#);
+        out.extend(column("function", json(&self.functions))?);
+        out.extend_from_slice(br#"},"type":{"#);
+        let types = self
+            .types
+            .iter()
+            .map(|types| {
+                types
+                    .as_ref()
+                    .map_or(Vec::new(), |types| types.write(codec))
+            })
+            .collect();
+        out.extend(column("type", types)?);
+        out.extend_from_slice(br#"},"alias":{"#);
+        let aliases = self
+            .aliases
+            .iter()
+            .map(|alias| alias.map_or(Vec::new(), |row| row.to_string().into_bytes()))
+            .collect();
+        out.extend(column("alias", aliases)?);
+        out.extend_from_slice(br#"},"generic_inverted_index":{"#);
+        let generics = self
+            .generics
+            .iter()
+            .map(|postings| {
+                let mut cell = Vec::new();
+                write_postings(codec, postings, &mut cell);
+                cell
+            })
+            .collect();
+        out.extend(column("generic_inverted_index", generics)?);
+        out.extend_from_slice(br#"}}')"#);
+
+        for (file, node) in tree.0 {
+            let mut contents = br#"rn_(""#.to_vec();
+            encode::write_base64_to_bytes(&node, &mut contents)?;
+            contents.extend_from_slice(br#"")"#);
+            rendered.push((dir.join(format!("{file}.js")), contents));
+        }
+        rendered.push((root.to_owned(), out));
+        Ok(rendered)
+    }
+
+    /// The part as it is kept in a [`SearchIndex`]: an object with the columns `n` (names), `p`
+    /// (paths), `e` (entries), `d` (descriptions), `f` (functions), `t` (types, as
+    /// `[inputs, outputs, unbox]`), `a` (aliases) and `g` (generics).
+    fn to_json(&self) -> JsonValue {
+        let postings = |postings: &Postings| {
+            JsonValue::Array(postings.iter().map(|rows| rows.clone().into()).collect())
+        };
+        jzon::object! {
+            n: self.names.clone(),
+            p: self.paths.clone(),
+            e: self.entries.clone(),
+            d: self.descs.clone(),
+            f: self.functions.clone(),
+            t: self.types.iter().map(|types| match types {
+                Some(types) => jzon::array![
+                    postings(&types.inputs),
+                    postings(&types.outputs),
+                    types.unbox,
+                ],
+                None => JsonValue::Null,
+            }).collect::<Vec<_>>(),
+            a: self.aliases.iter().map(|alias| match alias {
+                Some(row) => JsonValue::from(*row),
+                None => JsonValue::Null,
+            }).collect::<Vec<_>>(),
+            g: self.generics.iter().map(postings).collect::<Vec<_>>(),
+        }
+    }
+
+    /// Read back a part kept by [`to_json`](Self::to_json).
+    fn from_json(data: &JsonValue) -> Option<Table> {
+        fn postings(value: &JsonValue) -> Option<Postings> {
+            value
+                .members()
+                .map(|list| list.members().map(JsonValue::as_u32).collect())
+                .collect()
+        }
+        let strings = |key: &str| -> Option<Vec<String>> {
+            data[key]
+                .members()
+                .map(|value| value.as_str().map(str::to_owned))
+                .collect()
+        };
+        let values = |key: &str| data[key].members().cloned().collect::<Vec<_>>();
+        let table = Table {
+            names: strings("n")?,
+            paths: values("p"),
+            entries: values("e"),
+            descs: strings("d")?,
+            functions: values("f"),
+            types: data["t"]
+                .members()
+                .map(|types| match types {
+                    JsonValue::Null => Some(None),
+                    types => Some(Some(TypeData {
+                        inputs: postings(&types[0])?,
+                        outputs: postings(&types[1])?,
+                        unbox: types[2].as_bool()?,
+                    })),
+                })
+                .collect::<Option<_>>()?,
+            aliases: data["a"].members().map(JsonValue::as_usize).collect(),
+            generics: data["g"].members().map(postings).collect::<Option<_>>()?,
+        };
+        let len = table.names.len();
+        [
+            table.paths.len(),
+            table.entries.len(),
+            table.descs.len(),
+            table.functions.len(),
+            table.types.len(),
+            table.aliases.len(),
+        ]
+        .iter()
+        .all(|&column_len| column_len == len)
+        .then_some(table)
+    }
+}
+
+impl TypeData {
+    /// Read a cell of the `type` column: `["<inputs>", "<outputs>"]` with the postings in
+    /// base64, followed by `1` for types that can be unboxed.
+    fn read(codec: &dyn Codec, cell: &JsonValue) -> Option<Self> {
+        let postings = |value: &JsonValue| {
+            let mut bytes = Vec::new();
+            decode::read_base64_from_bytes(
+                value.as_str().unwrap_or_default().as_bytes(),
+                &mut bytes,
+            )
+            .ok()?;
+            read_postings(codec, &bytes)
+        };
+        Some(Self {
+            inputs: postings(&cell[0])?,
+            outputs: postings(&cell[1])?,
+            unbox: cell[2].as_u32() == Some(1),
+        })
+    }
+
+    /// Write the type data as a cell of the `type` column.
+    fn write(&self, codec: &dyn Codec) -> Vec<u8> {
+        let postings = |postings: &Postings| {
+            let (mut bytes, mut base64) = (Vec::new(), Vec::new());
+            write_postings(codec, postings, &mut bytes);
+            encode::write_base64_to_bytes(&bytes, &mut base64)
+                .expect("writing to memory cannot fail");
+            String::from_utf8(base64).expect("base64 is ASCII")
+        };
+        let mut cell = jzon::array![postings(&self.inputs), postings(&self.outputs)];
+        if self.unbox {
+            cell.push(1).expect("cell is an array");
+        }
+        cell.dump().into_bytes()
+    }
+
+    /// Renumber the functions, leaving out the ones `map` has no number for.
+    fn remap(&self, map: impl Fn(u32) -> Option<u32>) -> Self {
+        Self {
+            inputs: remap_postings(&self.inputs, &map),
+            outputs: remap_postings(&self.outputs, &map),
+            unbox: self.unbox,
+        }
+    }
+
+    /// Whether any of the `functions` takes or returns this type.
+    fn mentions(&self, functions: &BTreeSet<usize>) -> bool {
+        self.inputs
+            .iter()
+            .chain(&self.outputs)
+            .flatten()
+            .any(|&row| functions.contains(&(row as usize)))
+    }
+
+    /// Add the functions of `other`.
+    fn extend(&mut self, other: Self) {
+        extend_postings(&mut self.inputs, other.inputs);
+        extend_postings(&mut self.outputs, other.outputs);
+        self.unbox |= other.unbox;
+    }
+}
+
+/// Read a list of postings: each is either a count below `0x3a` followed by that many
+/// little-endian row numbers, or a roaring bitmap.
+fn read_postings(codec: &dyn Codec, mut bytes: &[u8]) -> Option<Postings> {
+    let mut postings = Vec::new();
+    while let Some(&count) = bytes.first() {
+        if count < 0x3a {
+            let end = 1 + usize::from(count) * 4;
+            let rows = bytes.get(1..end)?;
+            postings.push(
+                rows.chunks_exact(4)
+                    .map(|row| u32::from_le_bytes([row[0], row[1], row[2], row[3]]))
+                    .collect(),
+            );
+            bytes = &bytes[end..];
+        } else {
+            let (rows, len) = codec.read_bitmap(bytes)?;
+            postings.push(rows);
+            bytes = &bytes[len..];
+        }
+    }
+    Some(postings)
+}
+
+/// Write a list of postings, each in whichever of the two ways takes less space.
+fn write_postings(codec: &dyn Codec, postings: &Postings, out: &mut Vec<u8>) {
+    for rows in postings {
+        if rows.is_empty() {
+            out.push(0);
+            continue;
+        }
+        let start = out.len();
+        codec.write_bitmap(rows, out);
+        if out.len() - start > 1 + 4 * rows.len() && rows.len() < 0x3a {
+            out.truncate(start);
+            out.push(rows.len() as u8);
+            out.extend(rows.iter().flat_map(|row| row.to_le_bytes()));
+        }
+    }
+}
+
+/// Renumber the rows of `postings`, leaving out the ones `map` has no number for.
+fn remap_postings(postings: &Postings, map: impl Fn(u32) -> Option<u32>) -> Postings {
+    postings
+        .iter()
+        .map(|rows| {
+            let mut rows = rows.iter().filter_map(|&row| map(row)).collect::<Vec<_>>();
+            rows.sort_unstable();
+            rows
+        })
+        .collect()
+}
+
+/// Add the rows of `other` to `postings`, group by group.
+fn extend_postings(postings: &mut Postings, other: Postings) {
+    if postings.len() < other.len() {
+        postings.resize(other.len(), Vec::new());
+    }
+    for (rows, other) in postings.iter_mut().zip(other) {
+        rows.extend(other);
+        rows.sort_unstable();
+        rows.dedup();
+    }
+}
+
+/// The rows an entry refers to: its crate, module, exact module and parents.
+fn entry_refs(entry: &JsonValue) -> Vec<usize> {
+    let mut refs = entry[0].as_usize().into_iter().collect::<Vec<_>>();
+    refs.extend(
+        (2..=5)
+            .filter_map(|i| entry[i].as_usize())
+            .filter_map(|row| row.checked_sub(1)),
+    );
+    refs
+}
+
+/// Renumber the rows an entry refers to.
+fn remap_entry(entry: &JsonValue, map: impl Fn(usize) -> Option<usize>) -> JsonValue {
+    let mut entry = entry.clone();
+    if entry.is_null() {
+        return entry;
+    }
+    if let Some(row) = entry[0].as_usize() {
+        entry[0] = map(row).unwrap_or(row).into();
+    }
+    for i in 2..=5 {
+        if let Some(row) = entry[i].as_usize().and_then(|row| row.checked_sub(1)) {
+            entry[i] = map(row).map_or(0, |row| row + 1).into();
+        }
+    }
+    entry
+}
+
+/// The rows the types in a function's signature refer to.
+fn function_refs(function: &JsonValue) -> Vec<usize> {
+    let mut refs = Vec::new();
+    remap_signature(function[0].as_str().unwrap_or_default(), |row| {
+        refs.push(row);
+        Some(row)
+    });
+    refs
+}
+
+/// Renumber the rows the types in a function's signature refer to.
+fn remap_function(function: &JsonValue, map: impl FnMut(usize) -> Option<usize>) -> JsonValue {
+    let mut function = function.clone();
+    if let Some(signature) = function[0].as_str() {
+        function[0] = remap_signature(signature, map).into();
+    }
+    function
+}
+
+/// Renumber the types in a signature, which is made of `{` and `}` and of numbers in rustdoc's
+/// self-terminating hex: rows plus one, 0 for unknown types, and negative numbers for generics.
+fn remap_signature(signature: &str, mut map: impl FnMut(usize) -> Option<usize>) -> String {
+    let mut bytes = signature.as_bytes();
+    let mut out = String::with_capacity(signature.len());
+    while let Some(&c) = bytes.first() {
+        if c == b'{' || c == b'}' {
+            out.push(char::from(c));
+            bytes = &bytes[1..];
+            continue;
+        }
+        let Some((n, len)) = read_signed_vlqhex(bytes) else {
+            return signature.to_owned();
+        };
+        bytes = &bytes[len..];
+        let n = match usize::try_from(n).ok().and_then(|row| row.checked_sub(1)) {
+            Some(row) => map(row)
+                .and_then(|row| i32::try_from(row + 1).ok())
+                .unwrap_or(0),
+            None => n,
+        };
+        write_signed_vlqhex(n, &mut out);
+    }
+    out
+}
+
+/// Read a zig-zag encoded number written in self-terminating hex, where every digit but the
+/// last is written as `@` to `O`, and the last one as `` ` `` to `o`.
+pub(crate) fn read_signed_vlqhex(bytes: &[u8]) -> Option<(i32, usize)> {
+    let mut n = 0u32;
+    for (i, &c) in bytes.iter().enumerate() {
+        if !(b'@'..=b'o').contains(&c) {
+            return None;
+        }
+        n = (n << 4) | u32::from(c & 0xf);
+        if c >= b'`' {
+            let magnitude = i32::try_from(n >> 1).ok()?;
+            return Some((if n & 1 == 1 { -magnitude } else { magnitude }, i + 1));
+        }
+    }
+    None
+}
+
+/// Write a number the way [`read_signed_vlqhex`] reads it.
+fn write_signed_vlqhex(n: i32, out: &mut String) {
+    let value = (n.unsigned_abs() << 1) | u32::from(n < 0);
+    let digits = (32 - value.leading_zeros()).div_ceil(4).max(1);
+    for digit in (0..digits).rev() {
+        let hexit = (value >> (digit * 4)) & 0xf;
+        let base = if digit == 0 { b'`' } else { b'@' };
+        out.push(char::from(base + hexit as u8));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fsutil;
+
+    /// The `search.index/` directory of a fixture: `alpha` or `beta` documented on their own, or
+    /// `both` documented into one directory, by the rustdoc `version`.
+    fn fixture(version: &str, docs: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(format!("rustdoc-{version}"))
+            .join(docs)
+            .join(DIR)
+    }
+
+    /// Every file below `dir`, relative to it, with its contents.
+    fn files(dir: &Path) -> BTreeMap<PathBuf, Vec<u8>> {
+        fsutil::walk_files(dir)
+            .unwrap()
+            .into_iter()
+            .map(|rel| {
+                let contents = fs::read(dir.join(&rel)).unwrap();
+                (rel, contents)
+            })
+            .collect()
+    }
+
+    /// Lay out `index` as the files of an index in `dir`, relative to it.
+    fn rendered(codec: &dyn Codec, dir: &Path, index: &SearchIndex) -> BTreeMap<PathBuf, Vec<u8>> {
+        render(codec, &dir.join("root.js"), index)
+            .unwrap()
+            .into_iter()
+            .map(|(path, contents)| (path.strip_prefix(dir).unwrap().to_owned(), contents))
+            .collect()
+    }
+
+    /// Each generation of the index, by the rustdoc version its fixtures were written by.
+    const GENERATIONS: [(&str, &dyn Codec); 2] = [("1.95", &Stringdex005), ("1.97", &Stringdex006)];
+
+    #[test]
+    fn merges_crates_like_rustdoc() {
+        for (version, codec) in GENERATIONS {
+            let mut index = read(codec, &fixture(version, "alpha").join("root.js")).unwrap();
+            index.extend(read(codec, &fixture(version, "beta").join("root.js")).unwrap());
+            assert_eq!(index.keys().collect::<Vec<_>>(), ["alpha", "beta"]);
+            let both = fixture(version, "both");
+            assert!(
+                rendered(codec, &both, &index) == files(&both),
+                "rustdoc {version}"
+            );
+        }
+    }
+
+    #[test]
+    fn splits_and_joins_the_table_of_several_crates() {
+        for (version, codec) in GENERATIONS {
+            let both = fixture(version, "both");
+            let index = read(codec, &both.join("root.js")).unwrap();
+            assert_eq!(index.keys().collect::<Vec<_>>(), ["alpha", "beta"]);
+            assert!(
+                rendered(codec, &both, &index) == files(&both),
+                "rustdoc {version}"
+            );
+
+            // the order the parts are joined in does not matter once the rows are sorted
+            let table = Table::read(codec, &both.join("root.js")).unwrap();
+            let parts = table.split();
+            let joined = Table::join(parts.into_values().rev()).sorted();
+            assert_eq!(joined.names, table.names);
+            assert_eq!(joined.entries, table.entries);
+            assert_eq!(joined.functions, table.functions);
+            assert_eq!(joined.to_json(), table.to_json());
+        }
+    }
+
+    #[test]
+    fn keeps_the_paths_of_other_crates_in_each_part() {
+        let (version, codec) = GENERATIONS[0];
+        let table = Table::read(codec, &fixture(version, "both").join("root.js")).unwrap();
+        let parts = table.split();
+        for (crate_name, part) in &parts {
+            assert!(part.names.iter().any(|name| name == crate_name));
+            // the items of the other crate are only kept as paths, if at all
+            for row in 0..part.names.len() {
+                if let Some(owner) = part.crate_of(row) {
+                    assert_eq!(owner, crate_name);
+                }
+            }
+            assert_eq!(
+                Table::from_json(&part.to_json()).unwrap().to_json(),
+                part.to_json()
+            );
+        }
+    }
+
+    #[test]
+    fn sorts_rows_by_name_length_then_name() {
+        let mut table = Table::default();
+        for name in ["gamma", "b", "alpha", "a"] {
+            table.names.push(name.to_owned());
+            table.paths.push(jzon::array![1, ""]);
+            table.entries.push(JsonValue::Null);
+            table.descs.push(String::new());
+            table.functions.push(JsonValue::Null);
+            table.types.push(None);
+            table.aliases.push(None);
+        }
+        // `gamma` is an alias of `b`, and `alpha` a function taking `gamma`
+        table.aliases[0] = Some(1);
+        table.functions[2] = jzon::array!["{b}", []];
+        let sorted = table.sorted();
+        assert_eq!(sorted.names, ["a", "b", "alpha", "gamma"]);
+        assert_eq!(sorted.aliases[3], Some(1));
+        // rows are written plus one and zig-zag encoded: row 0 as `b`, and row 3 as `h`
+        assert_eq!(sorted.functions[2][0], "{h}");
+    }
+
+    #[test]
+    fn rejects_references_past_the_last_row() {
+        let (version, codec) = GENERATIONS[1];
+        let root = fixture(version, "both").join("root.js");
+        let table = Table::read(codec, &root).unwrap();
+        let len = table.names.len();
+        let row = table
+            .entries
+            .iter()
+            .position(|entry| !entry.is_null())
+            .unwrap();
+        let past_the_end = |n: usize| {
+            let mut signature = String::from("{");
+            write_signed_vlqhex(n as i32, &mut signature);
+            signature.push('}');
+            signature
+        };
+        let breakages: [&dyn Fn(&mut Table); 5] = [
+            &|table| table.entries[row][0] = len.into(),
+            &|table| table.entries[row][2] = (len + 1).into(),
+            &|table| table.functions[row] = jzon::array![past_the_end(len + 1), []],
+            &|table| table.aliases[row] = Some(len),
+            &|table| table.generics = vec![vec![vec![len as u32]]],
+        ];
+        for (i, breakage) in breakages.iter().enumerate() {
+            let mut broken = Table::read(codec, &root).unwrap();
+            breakage(&mut broken);
+            let dir = tempfile::tempdir().unwrap();
+            let broken_root = dir.path().join(DIR).join("root.js");
+            for (path, contents) in broken.render(codec, &broken_root).unwrap() {
+                fsutil::write_file(&path, contents).unwrap();
+            }
+            let err = Table::read(codec, &broken_root).unwrap_err();
+            assert!(
+                matches!(
+                    err.downcast_ref::<MergeError>(),
+                    Some(MergeError::InvalidFile { path, .. }) if *path == broken_root
+                ),
+                "breakage {i}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn vlqhex_round_trips() {
+        for n in [
+            0,
+            1,
+            -1,
+            7,
+            -8,
+            15,
+            16,
+            -16,
+            255,
+            4096,
+            -65536,
+            i32::MAX >> 1,
+        ] {
+            let mut out = String::new();
+            write_signed_vlqhex(n, &mut out);
+            assert_eq!(
+                read_signed_vlqhex(out.as_bytes()),
+                Some((n, out.len())),
+                "{n} as {out}"
+            );
+        }
+        // 0 is written as a single terminating digit, and 1 and -1 zig-zag to 2 and 3
+        for (n, hex) in [(0, "`"), (1, "b"), (-1, "c"), (8, "A`")] {
+            let mut out = String::new();
+            write_signed_vlqhex(n, &mut out);
+            assert_eq!(out, hex);
+        }
+        assert_eq!(read_signed_vlqhex(b"A"), None);
+        assert_eq!(read_signed_vlqhex(b"{"), None);
+    }
+
+    #[test]
+    fn remaps_signatures() {
+        // rows 0 and 1 swap places; generics and unknown types are left alone
+        let swap = |row: usize| Some(1 - row);
+        let mut signature = String::from("{");
+        for n in [1, 2, -1, 0] {
+            write_signed_vlqhex(n, &mut signature);
+        }
+        signature.push('}');
+        let mut expected = String::from("{");
+        for n in [2, 1, -1, 0] {
+            write_signed_vlqhex(n, &mut expected);
+        }
+        expected.push('}');
+        assert_eq!(remap_signature(&signature, swap), expected);
+        // signatures that cannot be read are kept as they are
+        assert_eq!(remap_signature("{z}", swap), "{z}");
+    }
+
+    #[test]
+    fn postings_round_trip() {
+        let many = (0..200).map(|row| row * 3).collect::<Vec<_>>();
+        let postings = vec![vec![], vec![4], vec![1, 2, 70000], many];
+        for (_, codec) in GENERATIONS {
+            let mut bytes = Vec::new();
+            write_postings(codec, &postings, &mut bytes);
+            assert_eq!(read_postings(codec, &bytes), Some(postings.clone()));
+        }
+        // a short list is written as a count and little-endian rows
+        let mut bytes = Vec::new();
+        write_postings(&Stringdex006, &vec![vec![4]], &mut bytes);
+        assert_eq!(bytes, [1, 4, 0, 0, 0]);
+        // a truncated list cannot be read
+        assert_eq!(read_postings(&Stringdex006, &[2, 4, 0, 0, 0]), None);
+    }
+
+    #[test]
+    fn type_data_round_trips() {
+        let types = TypeData {
+            inputs: vec![vec![0, 3]],
+            outputs: vec![vec![], vec![2]],
+            unbox: true,
+        };
+        for (_, codec) in GENERATIONS {
+            let cell = jzon::parse(std::str::from_utf8(&types.write(codec)).unwrap()).unwrap();
+            let read = TypeData::read(codec, &cell).unwrap();
+            assert_eq!(
+                (read.inputs, read.outputs, read.unbox),
+                (types.inputs.clone(), types.outputs.clone(), true)
+            );
+        }
+    }
+}
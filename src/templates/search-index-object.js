@@ -0,0 +1,3 @@
+var searchIndex = JSON.parse('{searchIndexJson}');
+if (typeof window !== 'undefined' && window.initSearch) {{window.initSearch(searchIndex)}};
+if (typeof exports !== 'undefined') {{exports.searchIndex = searchIndex}};
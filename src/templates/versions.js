@@ -0,0 +1,50 @@
+// Written by doc-merge: adds a menu to every page for switching to the same page in another
+// version of the documentation. The versions are listed in versions.json, next to this script.
+(function() {
+    var script = document.currentScript;
+    var current = script.getAttribute("data-doc-merge-version");
+    var root = new URL(".", script.src).href;
+    var page = "";
+    // the page may also have been reached through the alias to the newest version
+    [current, "latest"].forEach(function(dir) {
+        var base = root + encodeURIComponent(dir) + "/";
+        if (location.href.indexOf(base) === 0) {
+            page = location.href.slice(base.length);
+        }
+    });
+
+    function show(versions) {
+        var select = document.createElement("select");
+        select.id = "doc-merge-versions";
+        select.setAttribute("aria-label", "Documentation version");
+        select.style.font = "inherit";
+        versions.forEach(function(version) {
+            var option = document.createElement("option");
+            option.value = version;
+            option.textContent = version;
+            option.selected = version === current;
+            select.appendChild(option);
+        });
+        select.addEventListener("change", function() {
+            var dir = root + encodeURIComponent(select.value) + "/";
+            var target = dir + page;
+            // not every item exists in every version, so fall back to the version's index
+            fetch(target, { method: "HEAD" }).then(function(response) {
+                location.href = response.ok ? target : dir + "index.html";
+            }, function() {
+                location.href = target;
+            });
+        });
+        var menu = document.createElement("div");
+        menu.style.position = "fixed";
+        menu.style.top = "8px";
+        menu.style.right = "8px";
+        menu.style.zIndex = "100";
+        menu.appendChild(select);
+        document.body.appendChild(menu);
+    }
+
+    fetch(root + "versions.json").then(function(response) {
+        return response.json();
+    }).then(show, function() {});
+})();
@@ -0,0 +1,53 @@
+//! Skipping files that a merge would not change.
+//!
+//! Rewriting a file with the same contents still gives it a new modification time, which
+//! invalidates CDN caches and makes rsync transfer it again.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::MergeError;
+
+/// How to tell that a file in the destination is the same as the one that would replace it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Unchanged {
+    /// The files have the same size and modification time. This is cheap, but only works for
+    /// files that were copied by an earlier merge, which keeps the modification time of the
+    /// source.
+    Mtime,
+
+    /// The files have the same contents.
+    Content,
+}
+
+named_options!(Unchanged, "way to skip unchanged files", {
+    Mtime => "mtime",
+    Content => "content",
+});
+
+impl Unchanged {
+    /// Whether the file at `to` is the same as the file at `from`, which would be copied over it.
+    pub(crate) fn is_unchanged(self, from: &Path, to: &Path) -> Result<bool> {
+        let Ok(old) = fs::metadata(to) else {
+            return Ok(false);
+        };
+        let new = fs::metadata(from).map_err(MergeError::io(from))?;
+        if old.len() != new.len() {
+            return Ok(false);
+        }
+        Ok(match self {
+            Self::Mtime => old
+                .modified()
+                .ok()
+                .is_some_and(|t| new.modified().ok() == Some(t)),
+            Self::Content => {
+                fs::read(from).map_err(MergeError::io(from))?
+                    == fs::read(to).map_err(MergeError::io(to))?
+            }
+        })
+    }
+}
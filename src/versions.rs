@@ -0,0 +1,171 @@
+//! Sites holding the documentation of several versions, side by side.
+//!
+//! Each version is merged into its own `<dest>/<version>/` directory. The versions are listed in
+//! `<dest>/versions.json`, newest first, and every page loads `<dest>/versions.js`, which reads
+//! the list and adds a menu for switching to the same page in another version.
+
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Component, Path};
+
+use anyhow::{bail, Result};
+use jzon::JsonValue;
+
+use crate::{fsutil, html, latest, MergeError};
+
+/// The file listing the versions in the site, relative to the site.
+pub(crate) const MANIFEST: &str = "versions.json";
+
+/// The version menu script, relative to the site.
+pub(crate) const SCRIPT: &str = "versions.js";
+
+/// The attribute that marks the version menu's `<script>` tag in a page.
+const MARKER: &str = "data-doc-merge-version";
+
+/// Check that `version` can be used as the name of a directory in the site.
+pub(crate) fn check_name(version: &str) -> Result<()> {
+    if version == latest::DIR {
+        bail!(
+            "`{version}` cannot be used as a version, as it is the name of the alias to the \
+             newest version"
+        );
+    }
+    let mut components = Path::new(version).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) if !version.starts_with('.') => Ok(()),
+        _ => bail!("`{version}` cannot be used as a version, as it is not a plain directory name"),
+    }
+}
+
+/// Read the versions listed in the site at `dest`, newest first.
+pub(crate) fn read(dest: &Path) -> Result<Vec<String>> {
+    let path = dest.join(MANIFEST);
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path).map_err(MergeError::io(&path))?;
+    let json = jzon::parse(&content)
+        .map_err(|err| MergeError::invalid(&path, format!("invalid JSON: {err}")))?;
+    json.members()
+        .map(|version| {
+            version
+                .as_str()
+                .map(str::to_owned)
+                .ok_or_else(|| MergeError::invalid(&path, "versions must be strings").into())
+        })
+        .collect()
+}
+
+/// The versions listed in the site at `dest` once `version` is added to them, newest first.
+pub(crate) fn with(dest: &Path, version: &str) -> Result<Vec<String>> {
+    let mut versions = read(dest)?;
+    if !versions.iter().any(|known| known == version) {
+        versions.push(version.to_owned());
+    }
+    versions.sort_by(|a, b| compare(b, a));
+    Ok(versions)
+}
+
+/// List `versions` in the site at `dest`, and write the version menu script next to the list.
+pub(crate) fn record(dest: &Path, versions: &[String]) -> Result<()> {
+    let json = JsonValue::from(versions.to_vec());
+    fsutil::write_file(&dest.join(MANIFEST), json.pretty(2) + "\n")?;
+    fsutil::write_file(&dest.join(SCRIPT), include_str!("./templates/versions.js"))
+}
+
+/// Add the version menu to `page`, found at `rel` in the directory of `version`. Returns `None` if
+/// the page already has the menu, or is not a page it can be added to.
+pub(crate) fn inject(page: &str, rel: &Path, version: &str) -> Option<String> {
+    if page.contains(MARKER) {
+        return None;
+    }
+    let head_end = page.find("</head>")?;
+    // the script is in the site, one level above the directory of the version
+    let depth = rel.components().count();
+    let tag = format!(
+        "<script defer src=\"{}{SCRIPT}\" {MARKER}=\"{}\"></script>",
+        "../".repeat(depth),
+        html::escape(version),
+    );
+    Some(format!("{}{tag}{}", &page[..head_end], &page[head_end..]))
+}
+
+/// Compare two versions, treating runs of digits as numbers so that `1.10` comes after `1.9`.
+/// A pre-release such as `1.0.0-beta` comes before its release.
+fn compare(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (chunks(a), chunks(b));
+    loop {
+        match (a.next(), b.next()) {
+            (Some(a), Some(b)) if a != b => return a.cmp(&b),
+            (Some(_), Some(_)) => {}
+            (None, None) => return Ordering::Equal,
+            (Some(Chunk::Text(pre)), None) if pre.starts_with('-') => return Ordering::Less,
+            (None, Some(Chunk::Text(pre))) if pre.starts_with('-') => return Ordering::Greater,
+            (Some(_), None) => return Ordering::Greater,
+            (None, Some(_)) => return Ordering::Less,
+        }
+    }
+}
+
+/// A piece of a version: a number, or a run of anything else.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Chunk<'a> {
+    Text(&'a str),
+    Number(u64),
+}
+
+/// Split a version into numbers and the text between them.
+fn chunks(version: &str) -> impl Iterator<Item = Chunk<'_>> {
+    let mut rest = version;
+    std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        let digits = first.is_ascii_digit();
+        let end = rest
+            .find(|c: char| c.is_ascii_digit() != digits)
+            .unwrap_or(rest.len());
+        let (chunk, tail) = rest.split_at(end);
+        rest = tail;
+        Some(match chunk.parse() {
+            Ok(number) if digits => Chunk::Number(number),
+            _ => Chunk::Text(chunk),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_numbers_by_value() {
+        assert_eq!(compare("1.10", "1.9"), Ordering::Greater);
+        assert_eq!(compare("1.9.0", "1.10.0"), Ordering::Less);
+        assert_eq!(compare("v2", "v10"), Ordering::Less);
+        assert_eq!(compare("1.2.3", "1.2.3"), Ordering::Equal);
+    }
+
+    #[test]
+    fn longer_versions_come_after_their_prefix() {
+        assert_eq!(compare("1.0", "1.0.1"), Ordering::Less);
+        assert_eq!(compare("1.0.1", "1.0"), Ordering::Greater);
+    }
+
+    #[test]
+    fn pre_releases_come_before_their_release() {
+        assert_eq!(compare("1.0.0-beta", "1.0.0"), Ordering::Less);
+        assert_eq!(compare("1.0.0", "1.0.0-rc.1"), Ordering::Greater);
+        assert_eq!(compare("1.0.0-alpha", "1.0.0-beta"), Ordering::Less);
+        assert_eq!(compare("1.0.0-beta.2", "1.0.0-beta.10"), Ordering::Less);
+        assert_eq!(compare("1.0.0-rc.1", "0.9.0"), Ordering::Greater);
+    }
+
+    #[test]
+    fn sorts_versions_newest_first() {
+        let mut versions = ["0.9", "1.0.0-beta", "1.10", "main", "1.0.0", "1.9"];
+        versions.sort_by(|a, b| compare(b, a));
+        assert_eq!(
+            versions,
+            ["1.10", "1.9", "1.0.0", "1.0.0-beta", "0.9", "main"]
+        );
+    }
+}
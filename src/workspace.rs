@@ -0,0 +1,104 @@
+//! Discovery of documentation sources from a Cargo workspace.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::filter::Glob;
+
+/// The kinds of targets that `cargo doc` documents.
+const DOCUMENTED_KINDS: &[&str] = &["lib", "rlib", "dylib", "proc-macro", "bin"];
+
+/// A Cargo workspace, as described by `cargo metadata`.
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    /// The root directory of the workspace.
+    pub root: PathBuf,
+
+    /// The target directory that `cargo doc` writes to.
+    pub target_dir: PathBuf,
+
+    /// The names of the crates documented for the workspace members, as they appear in rustdoc
+    /// output (with dashes replaced by underscores).
+    pub crates: BTreeSet<String>,
+
+    /// The target triple the documentation is built for, if not the host.
+    pub target: Option<String>,
+}
+
+impl Workspace {
+    /// Read the workspace containing `path` with `cargo metadata`.
+    pub fn discover(path: &Path) -> Result<Self> {
+        let output = Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+            .args([
+                "metadata",
+                "--format-version",
+                "1",
+                "--no-deps",
+                "--offline",
+            ])
+            .current_dir(path)
+            .output()
+            .with_context(|| format!("Could not run `cargo metadata` in {}", path.display()))?;
+        if !output.status.success() {
+            bail!(
+                "`cargo metadata` failed in {}:\n{}",
+                path.display(),
+                String::from_utf8_lossy(&output.stderr).trim_end()
+            );
+        }
+        let metadata = jzon::parse(&String::from_utf8_lossy(&output.stdout))?;
+        let field = |name: &str| {
+            metadata[name]
+                .as_str()
+                .map(PathBuf::from)
+                .ok_or_else(|| anyhow!("`cargo metadata` output has no {name}"))
+        };
+
+        let members = metadata["workspace_members"]
+            .members()
+            .filter_map(|id| id.as_str())
+            .collect::<BTreeSet<_>>();
+        let crates = metadata["packages"]
+            .members()
+            .filter(|package| {
+                package["id"]
+                    .as_str()
+                    .is_some_and(|id| members.contains(id))
+            })
+            .flat_map(|package| package["targets"].members())
+            .filter(|target| {
+                target["kind"]
+                    .members()
+                    .any(|kind| kind.as_str().is_some_and(|k| DOCUMENTED_KINDS.contains(&k)))
+            })
+            .filter_map(|target| target["name"].as_str())
+            .map(|name| name.replace('-', "_"))
+            .collect();
+
+        Ok(Self {
+            root: field("workspace_root")?,
+            target_dir: field("target_directory")?,
+            crates,
+            target: None,
+        })
+    }
+
+    /// The directory `cargo doc` writes the workspace's documentation to.
+    pub fn doc_dir(&self) -> PathBuf {
+        match &self.target {
+            Some(target) => self.target_dir.join(target).join("doc"),
+            None => self.target_dir.join("doc"),
+        }
+    }
+
+    /// Only keep the crates matching one of `globs`. Does nothing if `globs` is empty.
+    pub fn retain_matching(&mut self, globs: &[Glob]) {
+        if !globs.is_empty() {
+            self.crates
+                .retain(|name| globs.iter().any(|glob| glob.matches(name)));
+        }
+    }
+}
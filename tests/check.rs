@@ -0,0 +1,53 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Run `doc-merge check` on the site at `dest`.
+fn check(dest: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_doc-merge"))
+        .arg("check")
+        .arg("--dest")
+        .arg(dest)
+        .current_dir(dest)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn fails_on_broken_links() {
+    let site = tempfile::tempdir().unwrap();
+    fs::create_dir(site.path().join("alpha")).unwrap();
+    fs::write(
+        site.path().join("alpha/index.html"),
+        r#"<a href="struct.Gone.html">gone</a>"#,
+    )
+    .unwrap();
+
+    let output = check(site.path());
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "alpha:\n  alpha/index.html: struct.Gone.html (no such file)\n"
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Found 1 broken links in 1 pages"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn passes_a_whole_site() {
+    let site = tempfile::tempdir().unwrap();
+    fs::write(
+        site.path().join("index.html"),
+        r##"<a href="#top" id="top">"##,
+    )
+    .unwrap();
+
+    let output = check(site.path());
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, "Checked 1 links in 1 pages; none are broken\n");
+}
@@ -0,0 +1 @@
+window.SIDEBAR_ITEMS = {"mod":["sub"],"struct":["Thing"]};
\ No newline at end of file
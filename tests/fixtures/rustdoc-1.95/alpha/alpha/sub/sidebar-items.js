@@ -0,0 +1 @@
+window.SIDEBAR_ITEMS = {};
\ No newline at end of file
@@ -0,0 +1,2 @@
+window.ALL_CRATES = ["alpha"];
+//{"start":21,"fragment_lengths":[7]}
\ No newline at end of file
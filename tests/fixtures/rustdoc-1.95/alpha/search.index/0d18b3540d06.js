@@ -0,0 +1 @@
+rn_("BQHAAAABFQBABgAIABQAGQAdAGVvBQHAAACSHgAgCQAeAB8AbnQVAkAAABASABcAAx0Alx4AZGl0CgANAK8BhqAQAAAAG6AAAAAAC6AAAAAAAlQBRA==")
\ No newline at end of file
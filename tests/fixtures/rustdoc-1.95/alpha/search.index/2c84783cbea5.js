@@ -0,0 +1 @@
+rn_("BQBAAAADGwBlFQAFAcAAABAQABYAAh0AZG5HAQCHsAAAEgAFoGAAAAAboCAAAAAboDAAAAAPsHAAHgABKCEH0woAAAADDg==")
\ No newline at end of file
@@ -0,0 +1 @@
+rn_("FQFAAAASGgAcABISABcAbW4OABEAFQBDAAASGgAcAG0OABEAFQFBAAADHQCXHgBpdAoADQAxQAAABQAHABMAGABnBQCHoEAAAAAVsEAAEgAFsFAAHgABAHEZ1QYAAAACDAUE")
\ No newline at end of file
@@ -0,0 +1 @@
+rd_("ealpha")
\ No newline at end of file
@@ -0,0 +1 @@
+rd_("lA submodule.AoReturns the argument unchanged.BaCalls <code>U::from(self)</code>.hA thing.A`The alpha crate.")
\ No newline at end of file
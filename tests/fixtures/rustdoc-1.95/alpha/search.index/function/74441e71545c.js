@@ -0,0 +1 @@
+rd_("A`[\"{cc{}}\",[\"T\"]]Aa[\"{{}c{}}\",[\"U\"]]Ae[\"{{{Ch{Ah}}}Ah}\",[]]Ai[\"{Ch{{Ch{c}}}{}}\",[\"T\"]]m[\"{ChBb}\",[]]Aa[\"{Chc{}}\",[\"T\"]]An[\"{c{{B`{e}}}{}{}}\",[\"U\",\"T\"]]Ai[\"{{}{{B`{c}}}{}}\",[\"U\"]]Ba[\"{{{Ch{h}}}{{Ch{hc}}}{}}\",[\"T\"]]Al[\"{{Ch{Ch{hc}}}Ad{}}\",[\"T\"]]Ag[\"{{Ch{Bl{hd}}}Ad}\",[]]")
\ No newline at end of file
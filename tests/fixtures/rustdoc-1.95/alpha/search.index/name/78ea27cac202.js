@@ -0,0 +1 @@
+rd_("b()bu8cAnycmutcsubdFromdIntodfromdintoduniteCloneeThingealphaeclonefBorrowfResultfTypeIdfborrowgToOwnedgTryFromgTryIntogpointergtype_idhto_ownedhtry_fromhtry_intoiBorrowMutireferencejborrow_mutjclone_intomCloneToUninitoclone_to_uninit")
\ No newline at end of file
@@ -0,0 +1 @@
+rd_("b()bu8canycmutcsubdfromdinto10dunitecloneethingealpha2fborrowfresultftypeid2gtoownedgtryfromgtryintogpointer4321iborrowmutireference1icloneintomclonetouninit0")
\ No newline at end of file
@@ -0,0 +1 @@
+rr_('{"normalizedName":{"I":"BQJAAAATEwAYABMUABkAExAAFgBmaXACABUBQAAAEhoAHAASEgAXAG1uDgARABUBQgAAAx0Alx4AaXQKAA0AGwOgIAAAAAygAAAAAA9vcHQFAcAAAAAMAAILAGFpMUAAABAAEgAWABcAAIFpAQHAAAAAAAmwIAAeAAF0biKAAgPAAAAAAAGgAAAAAASgEAAAAA+AAAMBcAI4bmJsdBBEcGVpZAAAEAAWAAQBwXkAABNyb20TABgAE250bxQAGQBmaRBEd25lZAAAEgAXABIAAQGwUAAeAAFvdQDVBgAAAAIMBQQAQ2luZwAACwD2AAAEAaAQAAAAFWhvcnllANcDAAAABgYLAgIBAIF1AQHAAAAAAASgEAAAAA9ibAUBwAAAExMAGAATFAAZAGZpFQBCAAASGgAcAG0OABEAFQBAAAASGgAcAG0OABEAMUAAAAUABwATABgA+wJtdwBDdWx0AAAPAABGZXJlbmNlAAAbADKAAgGgIAAAABtmc27yAAEDZW9yeQABFQAAAABFaW50ZXIAABUAEoABArAgABAABqAQAAAADG9laAABdAAAAQMAAADSGgAAAALyAAEAdQDUBQAAAAIMBQAAAADSBgAAAALTFAAAAAUEEoABAaAQAAAAFW9lEoABAqAAAAAAC7AQAB4AAXRnaRKAAQKwAAAQAAaAAAkBUAFuZHQAAm9tAADSBQAAAALSEwAAAAUSgAEBoFAAAAAbcmUUAUNvbmUAAANudG8dAJdvdW5pbml0HgBpdAoADQASgAEBoAAAAAAbbGUUAERycm93AAASdXQaABwAbQ4AEQDyAAEAbwABBAAAAABDcGhhAAAMABIAAgDnkAAAAAJsbgABDAAAAACADAvikAAAAABkbU7sefpvNsmd6OJQWBB/7mxLzAbxpl4VL+Qtaqo+7YIfcHQsBbxIyXs8eml2HwcBbjc1AbQGGRmhHEJTO9MeTq6gAAAAAACgAAAAAAF1qJUZpLQshHg8vqWgAAAAAAt+7n/mNOdcPzHSgC8NGLNUDQY7swlJH2kMd1UjCZJsMV5vxDAoYWJjZmltcHJzdHUpOGRlZ2hsbm93eQ==","N":"B`","E":"OjAAAAAAAAA=","H":"cn4S7yc5"},"crateNames":{"N":"a","E":"OjAAAAAAAAA=","H":"m5jeQ63B"},"name":{"N":"B`","E":"OjAAAAAAAAA=","H":"eOonysIC"},"path":{"N":"Ad","E":"OjAAAAEAAAAAAAsAEAAAAAQABwAIAA0AEQAWABcAGAAZABwAHQAfAA==","H":"dU1iQ/Nx"},"entry":{"N":"n","E":"OzAAAAEAABEABwAAAAMABQABAAkAAQAOAAIAEgADABoAAQAeAAAA","H":"5eRCqG3c"},"desc":{"N":"e","E":"OzAAAAEAABoABAAAAAMABQABAAkAAQANABIA","H":"EIm5yVjk"},"function":{"N":"k","E":"OzAAAAEAABQABgAAAAYACQADAA4AAgASAAMAGgABAB4AAAA=","H":"dEQecVRc"},"type":{"N":"i","E":"OzAAAAEAABYABwACAAAABAAEAAoAAAAMAAIAEQADABYABAAcAAMA","H":"S0mCUhx8"},"alias":{"N":"`","E":"OzAAAAEAAB8AAQAAAB8A","H":"p2IVDFMs"},"generic_inverted_index":{"N":"b","E":"OjAAAAAAAAA=","H":"iPsefUPr"}}')
\ No newline at end of file
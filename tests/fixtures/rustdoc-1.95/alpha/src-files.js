@@ -0,0 +1,2 @@
+createSrcSidebar('[["alpha",["",[["sub",[],["mod.rs"]]],["lib.rs"]]]]');
+//{"start":19,"fragment_lengths":[49]}
\ No newline at end of file
@@ -0,0 +1 @@
+search-63369b7b.js
@@ -0,0 +1 @@
+storage-41dd4d93.js
@@ -0,0 +1 @@
+stringdex-b897f86f.js
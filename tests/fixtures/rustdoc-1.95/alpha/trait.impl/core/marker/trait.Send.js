@@ -0,0 +1,9 @@
+(function() {
+    const implementors = Object.fromEntries([["alpha",[["impl <a class=\"trait\" href=\"https://doc.rust-lang.org/1.95.0/core/marker/trait.Send.html\" title=\"trait core::marker::Send\">Send</a> for <a class=\"struct\" href=\"alpha/struct.Thing.html\" title=\"struct alpha::Thing\">Thing</a>",0,1,["alpha::Thing"]]]]]);
+    if (window.register_implementors) {
+        window.register_implementors(implementors);
+    } else {
+        window.pending_implementors = implementors;
+    }
+})()
+//{"start":59,"fragment_lengths":[271]}
\ No newline at end of file
@@ -0,0 +1 @@
+window.SIDEBAR_ITEMS = {"struct":["Other"]};
\ No newline at end of file
@@ -0,0 +1,2 @@
+window.ALL_CRATES = ["beta"];
+//{"start":21,"fragment_lengths":[6]}
\ No newline at end of file
@@ -0,0 +1 @@
+rn_("BQHAAAABFABABQAIABMAGAAcAGVvBQHAAACSHQAgCQAdAB4AbnQVAkAAABARABYAAxwAlx0AZGl0CgAMAOuFoBAAAAAaoAAAAAACFAFE")
\ No newline at end of file
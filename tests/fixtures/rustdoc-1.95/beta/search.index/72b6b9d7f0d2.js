@@ -0,0 +1 @@
+rn_("BQHAAAAABgCWHQBhbxUAQAAAAxoAZQsAFAAFAcAAABAPABUAAhwAZG5HBQCHsAAAEQAFoGAAAAAaoCAAAAAaoDAAAAAOKCEH0woAAAACDg==")
\ No newline at end of file
@@ -0,0 +1 @@
+rd_("Aa[6,3,0,0,0,0,0,0]Ac[6,13,7,7,12,5,0,0]Ac[6,13,7,7,12,6,0,0]Aa[6,5,7,0,0,0,0,0]Ad[6,13,7,7,12,11,0,0]Ad[6,13,7,7,12,14,0,0]Ac[6,13,7,7,12,3,0,0]Ad[6,13,7,7,12,18,0,0]Ad[6,13,7,7,12,19,0,0]Ad[6,13,7,7,12,20,0,0]Ad[6,13,7,7,12,26,0,0]3Ad[6,13,7,7,12,30,0,1]")
\ No newline at end of file
@@ -0,0 +1 @@
+rb_("QmYAAQgAAAADBwAAABYAAAAYAAAAAhAAAAAXAAAAAAIbAAAAHAAAAGgAAAABFwAAAA==")
\ No newline at end of file
@@ -0,0 +1 @@
+rr_('{"normalizedName":{"I":"BQJAAAATEgAXABMTABgAEw8AFQBmaXACABUBQAAAEhkAGwASEQAWAG1uDQAQABUBQgAAAxwAlx0AaXQKAAwAGwKgAAAAAA5vdDFAAAAPABEAFQAWAACBaQEBwAAAAAAJsCAAHQABdG4igAICwAAAAAABoBAAAAAOgAADAWACOG5sdBBEcGVpZAAADwAVAAQBwXkAABNyb20SABcAE250bxMAGABmaRBEd25lZAAAEQAWABIAAQGwUAAdAAFvdQDVBQAAAAMLBQRyAIODoAAAAAAGoBAAAAAUoCAAAAALAEBBkQAAANcDAAAABgULAgIBBQHAAAATEgAXABMTABgAZmkVAEIAABIZABsAbQ0AEAAVAEAAABIZABsAbQ0AEAAxQAAABAAHABIAFwD7Am13AEN1bHQAAA4AAEZlcmVuY2UAABoAMoACAaAgAAAAGmZzbvIAAQNlb3J5ANILAAAACQBFaW50ZXIAABQAEoABAbAgAA8ABm9lFQBDAAASGQAbAG0NABAAFQFBAAADHACXHQBpdAoADAAAQ2hlcgAACwDUCgCBhwCgQAAAABQKAbBAABEABQKwUAAdAAEYAAAEAHEZANUFAAAAAwsFBAABdAAAAQMAAADSGQAAAALyAAEAdQDUBAAAAAMLBQAAAADSBQAAAAPTEwAAAAUEEoABAaAQAAAAFG9lEoABAbAQAB0AAXRpEoABArAAAA8ABoAACQFAAW5kdAACb20AANIEAAAAA9ISAAAABRKAAQGgUAAAABpyZRQBQ29uZQAAA250bxwAl291bmluaXQdAGl0CgAMABKAAQGgAAAAABpsZRQARHJyb3cAABJ1dBkAGwBtDQAQAABCdGEAAAYA8oACAGVvAAABAOeQAAAAAm4AAQYAAAAAgAwK4pAAAAAAHOZZrIdVbEPVCyEFMjXU/XuHPwsmhFmDV8+pAB2yF93uJz+5XhMsT2M6L0GheniyOxbWHeo+FQPWPGYjfdazeVD5oAAAAAAAoAAAAAABM4XTwrLYcra51/DSoCAAAAALWJGDUngTPCI+qyfGoDAAAAAOSyInvcynGgllqjg3KGFiY2ZpbW9wcnR1KThkZWhsbnN3eQ==","N":"Ao","E":"OjAAAAAAAAA=","H":"r3Z3qc75"},"crateNames":{"N":"a","E":"OjAAAAAAAAA=","H":"Pdm7T48E"},"name":{"N":"Ao","E":"OjAAAAAAAAA=","H":"6ii3hXlP"},"path":{"N":"Ad","E":"OjAAAAEAAAAAAAoAEAAAAAcACAAMABAAFQAWABcAGAAbABwAHgA=","H":"eTU6qUnW"},"entry":{"N":"m","E":"OzAAAAEAABEABgAAAAUACQABAA0AAgARAAMAGQABAB0AAAA=","H":"iBBkmMHh"},"desc":{"N":"d","E":"OzAAAAEAABoAAwAAAAUACQABAAwAEgA=","H":"BWOSlJm/"},"function":{"N":"k","E":"OzAAAAEAABMABgAAAAYACQACAA0AAgARAAMAGQABAB0AAAA=","H":"ymaFlu7z"},"type":{"N":"i","E":"OzAAAAEAABUABwACAAAABAAEAAoAAAAMAAEAEAADABUABAAbAAMA","H":"aMfAPKWR"},"alias":{"N":"`","E":"OzAAAAEAAB4AAQAAAB4A","H":"p2IVDFMs"},"generic_inverted_index":{"N":"b","E":"OjAAAAAAAAA=","H":"bBrjoaNv"}}')
\ No newline at end of file
@@ -0,0 +1 @@
+rd_("Ak[\"\",\"AAAAAAACHAAAAB4AAAA=\"]Ag[\"AAAAAAABHgAAAA==\",\"\"]Bo[\"AAAAAAADGwAAABwAAAAeAAAA\",\"AAAAAAABGwAAAA==\"]2Ao[\"AAAAAQwAAAA=\",\"AAAAAQwAAAA=\"]Ai[\"\",\"AAABGAAAAAEXAAAA\",1]Ac[\"\",\"AAABFQAAAA==\"]Ai[\"AAAAAAABHgAAAA==\",\"\",1]Dm[\"AAACFQAAABYAAAACDAAAABAAAAAAAxsAAAAcAAAAHgAAAA==\",\"AAAAARAAAAAAARsAAAA=\",1]")
\ No newline at end of file
@@ -0,0 +1,2 @@
+createSrcSidebar('[["beta",["",[],["lib.rs"]]]]');
+//{"start":19,"fragment_lengths":[27]}
\ No newline at end of file
@@ -0,0 +1 @@
+main-5013f961.js
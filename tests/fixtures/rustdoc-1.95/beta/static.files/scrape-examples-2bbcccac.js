@@ -0,0 +1 @@
+scrape-examples-2bbcccac.js
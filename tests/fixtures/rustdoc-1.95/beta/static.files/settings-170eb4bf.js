@@ -0,0 +1 @@
+settings-170eb4bf.js
@@ -0,0 +1 @@
+src-script-813739b1.js
@@ -0,0 +1,9 @@
+(function() {
+    const implementors = Object.fromEntries([["beta",[["impl <a class=\"trait\" href=\"https://doc.rust-lang.org/1.95.0/core/marker/trait.Sync.html\" title=\"trait core::marker::Sync\">Sync</a> for <a class=\"struct\" href=\"beta/struct.Other.html\" title=\"struct beta::Other\">Other</a>",0,1,["beta::Other"]]]]]);
+    if (window.register_implementors) {
+        window.register_implementors(implementors);
+    } else {
+        window.pending_implementors = implementors;
+    }
+})()
+//{"start":59,"fragment_lengths":[267]}
\ No newline at end of file
@@ -0,0 +1,2 @@
+window.ALL_CRATES = ["alpha","beta"];
+//{"start":21,"fragment_lengths":[7,7]}
\ No newline at end of file
@@ -0,0 +1 @@
+rn_("BQHAAAAABwCmKgBhbxUAQAAAAyUAZQ4AGwAFAcAAACAVABwAHQCSKABkbkcFAIeAABgAYAGgYAAAACWgIAAAACWgMAAAABQoIQfUDQAAAAQBEw==")
\ No newline at end of file
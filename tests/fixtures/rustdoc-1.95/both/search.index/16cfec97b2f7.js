@@ -0,0 +1 @@
+rn_("BQHAAAABGwBwBgAKAAsAGgAiACMAKAApAGVvBQHAAACiKgAwDAAqACsALABudCUCQAAAIBgAHgAfAJMoAKcqAGRpdA0AEQASAK8BhqAQAAAAJaAAAAAAD6AAAAAAAlQBRA==")
\ No newline at end of file
@@ -0,0 +1 @@
+rd_("ealphadbeta")
\ No newline at end of file
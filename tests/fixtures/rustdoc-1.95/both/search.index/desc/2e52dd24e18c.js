@@ -0,0 +1 @@
+rd_("lA submodule.oThe beta crate.AoReturns the argument unchanged.0BaCalls <code>U::from(self)</code>.0nAnother thing.hA thing.A`The alpha crate.")
\ No newline at end of file
@@ -0,0 +1 @@
+rd_("b()bu8cAnycmutcsubdFromdIntodbetadfrom0dinto0duniteCloneeOthereThingealphaeclone0fBorrowfResultfTypeIdfborrow0gToOwnedgTryFromgTryIntogpointergtype_id0hto_owned0htry_from0htry_into0iBorrowMutireferencejborrow_mut0jclone_into0mCloneToUninitoclone_to_uninit0")
\ No newline at end of file
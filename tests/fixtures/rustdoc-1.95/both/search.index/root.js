@@ -0,0 +1 @@
+rr_('{"normalizedName":{"I":"BQJAAAAjGQAgACEAIxoAIgAjACMVABwAHQBmaXACACUBQAAAIiQAJgAnACIYAB4AHwBtbhMAFgAXACUBQgAAkygApyoAaXQNABEAEgAbA6AgAAAAEKAAAAAAFG9wdAUCwAAAABAAAQ4AAg8AYWVpUUAAABUAGAAcAB0AHgAfADFAAAADACQAJgAnAKFCAAAqACKBaQEBwAAAAAAMdG4mAYACA8AAAAAAAaAAAAAABKAQAAAAFDhuYmx0IERwZWlkAAAVABwAHQAEAcF5AAAjcm9tGQAgACEAI250bxoAIgAjAGZpoUUAACoAIER3bmVkAAAYAB4AHwDyAAEBb3UAOjAAAAEAAAAAAAcAEAAAAAYACgALABoAIgAjACgAKQAAQm5nAAAPABKAAQGgEAAAAA5pZfYAAAQCoAAAAAAHoBAAAAAbaG9yeWFlADowAAABAAAAAAAIABAAAAADAAwAFAAkACYAJwAqACsALAAAgXUBAcAAAAAABKAQAAAAFGJsBQHAAAAjGQAgACEAIxoAIgAjAGZpJQBCAAAiJAAmACcAbRMAFgAXACUAQAAAIiQAJgAnAG0TABYAFwBRQAAABQAIAAkAGQAgACEA+wJtdwBDdWx0AAAUAABGZXJlbmNlAAAlADKAAgGgIAAAACVmc27yAAEDZW9yeQDSDgAAAA0hQgAAFQAcAB0AAEVpbnRlcgAAGwAygAECoBAAAAAQb2VoJQBDAAAiJAAmACcAbRMAFgAXACFEAAAYAB4AHwAlAUEAAJMoAKcqAGl0DQARABIAAENoZXIAAA4A1A8AgYcAoEAAAAAbDAECAxYhAAAEAHEZADowAAABAAAAAAAHABAAAAAGAAoACwAaACIAIwAoACkAAAF0AAABAwAAANMkAAAAAgHyAAEAdQDWBQAAAAMBEAcBMUAAAAwAKgArACwAoUEAACoAAAAAANMGAAAABAHVGgAAAAgBBQESgAEBoBAAAAAbb2VSgAECoAAAAAAPdGdpUoABAoAAFQBwAW5kdAACb20AANMFAAAAAwHTGQAAAAcBEoABAaBQAAAAJXJlJAFDb25lAACTbnRvKACnb3VuaW5pdCoAaXQNABEAEgASgAEBoAAAAAAlbGUkAERycm93AAAidXQkACYAJwBtEwAWABcAAEJ0YQAABwDyAAIAZW8AAQQAAAAAQ3BoYQAAEAASAAIA55AAAAACbG4A0gcAAAAJAIANCuKQAAAAADM9d3h9fxOuClAaPGRvd3/r7VE2ACCE1h31VV4viWXu3NjN9mvZYtTJ1R3eaF/fQmgu2b5qTAuiBgCNGQbT53DEJRL7M2ZYWqAAAAAAAKAAAAAAAWHE6xqXTRBN2YVF4aAAAAAAD3N/qhkCEFWb0wO0RRbP7Jey90oUi5MKFSnWYis6cChhYmNmaW1vcHJzdHUpOGRlZ2hsbnd5","N":"Bm","E":"OjAAAAAAAAA=","H":"tRhPfREZ"},"crateNames":{"N":"b","E":"OjAAAAAAAAA=","H":"lv/H8QgM"},"name":{"N":"Bm","E":"OjAAAAAAAAA=","H":"uwarQpa4"},"path":{"N":"Af","E":"OzAAAAEAABYABwAEAAAACAADABEAAQAWAAEAHAAHACYAAwArAAEA","H":"sUef4tCI"},"entry":{"N":"Ak","E":"OzAAAAEAABEABwAAAAMABQABAAwAAQATAAIAGAADACQAAQAqAAAA","H":"NCFAX1JN"},"desc":{"N":"i","E":"OzAAAAEAACMABAAAAAMABQABAAwAAQARABsA","H":"LlLdJOGM"},"function":{"N":"Af","E":"OzAAAAEAABYABgAAAAcADAAEABMAAgAYAAMAJAABACoAAAA=","H":"z80QyyT8"},"type":{"N":"j","E":"OzAAAAEAACIABwACAAAABAAHAA0AAAAQAAMAFgAEABwACAAmAAYA","H":"k0k7z1QK"},"alias":{"N":"`","E":"OzAAAAEAACwAAQAAACwA","H":"p2IVDFMs"},"generic_inverted_index":{"N":"b","E":"OjAAAAAAAAA=","H":"8EPPLyaW"}}')
\ No newline at end of file
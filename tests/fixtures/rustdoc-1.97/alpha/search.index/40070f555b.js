@@ -0,0 +1 @@
+rn_("BYrAAAARFQBQBgAIABQAGQAdAAUCwAAAoh4AMAkAHgAfAG50JQPAAAAgEgAXABMdAKceAGRpdAoADQCvAf1UAUTAAAAAG+AAAAAL4AAAAAI=")
\ No newline at end of file
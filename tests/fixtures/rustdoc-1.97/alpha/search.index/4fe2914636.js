@@ -0,0 +1 @@
+rn_("occAAB4AFYLAAAATGwAVAAUCwAAAIBAAFgASHQBkbiHAAAASABcAVwUA/SghB8UAAAAbwQAAABvCAAAAD9MKAAAAAw4=")
\ No newline at end of file
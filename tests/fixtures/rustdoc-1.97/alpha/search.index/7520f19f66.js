@@ -0,0 +1 @@
+rn_("JQLAAAAiGgAcACISABcAbW4OABEAocUAAB4AJQHDAAAiGgAcAG0OABEAIcQAABIAFwAlAsEAABMdAKceAGl0CgANAEHAAAAFAAcAEwAYAOcHAP0AcRnDAAAAFdUGAAAAAgwFBA==")
\ No newline at end of file
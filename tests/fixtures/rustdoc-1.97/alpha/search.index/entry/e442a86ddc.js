@@ -0,0 +1 @@
+rd_("Ac[12,2,13,0,0,0,0,0]Af[12,13,13,13,12,6,0,0]Af[12,13,13,13,12,7,0,0]Ac[12,5,13,0,0,0,0,0]Ab[12,3,0,0,0,0,0,0]Ag[12,13,13,13,12,11,0,0]Ag[12,13,13,13,12,15,0,0]Af[12,13,13,13,12,3,0,0]Ag[12,13,13,13,12,19,0,0]Ag[12,13,13,13,12,20,0,0]Ag[12,13,13,13,12,21,0,0]Ag[12,13,13,13,12,27,0,0]3Ag[12,13,13,13,12,31,0,1]")
\ No newline at end of file
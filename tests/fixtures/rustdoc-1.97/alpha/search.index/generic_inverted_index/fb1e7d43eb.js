@@ -0,0 +1 @@
+rb_("QmYAAQgAAAADBwAAABcAAAAZAAAAAhEAAAAYAAAAAAIcAAAAHQAAAGgAAAABGAAAAA==")
\ No newline at end of file
@@ -0,0 +1 @@
+rd_("f[1,\"\"]0A`[10,\"core::any\"]f[0,\"\"]Ad[10,\"core::convert\"]03Ab[10,\"core::clone\"]Ac[5,\"alpha\",\"alpha\"]f[3,\"\"]Ac[10,\"core::borrow\"]Ba[6,\"core::result\",\"core::result\"]Ak[5,\"core::any\",\"core::any\"]Ad[10,\"alloc::borrow\"]77:3:6")
\ No newline at end of file
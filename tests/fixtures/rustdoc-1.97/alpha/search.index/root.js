@@ -0,0 +1 @@
+rr_('{"normalizedName":{"I":"FQPAAAAjEwAYACMUABkAIxAAFgBmaXACACUCwAAAIhoAHAAiEgAXAG1uDgARACUCwgAAEx0Apx4AaXQKAA0AGwPBAAAADOAAAAAPb3B0BYXAAAAQDAASCwBBwAAAEAASABYAFwAxwAAAAwAaABwAocIAAB4AIoFpAQGgAAAACXRuJgGAAsABgaAAAAAB4AAAAATAAAAADzhuIMRwZWlkAAAQABYABALBeQAAI3JvbRMAGAAjbnRvFAAZAGZpocUAAB4AIMR3bmVkAAASABcA8gCIkADVBgAAAAIMBQQQw2luZwAACwD2AAD9gEBBgsAAAAAVANcDAAAABgYLAgIBAIF1AQGgAAAABMAAAAAPYmwFAsAAACMTABgAIxQAGQBmaSUBwgAAIhoAHABtDgARACUBwAAAIhoAHABtDgARAEHAAAAFAAcAEwAYAPsCbXcQw3VsdAAADwAQxmVyZW5jZQAAGwAygAIBwQAAABtmc27yAIIDb3J5AAEVAAAAIcIAABAAFgAQxWludGVyAAAVADKAiALAAAAADGVoAEF0AQMAAADSGgAAAALyAJAAANQFAAAAAgwFMcAAAAkAHgAfACHAAAAQABYAocEAAB4AAEDSBgAAAALTFAAAAAUEEoCIgsAAAAAVUoABAuAAAAALdGdp8oABAm5kdABCb23SBQAAAALSEwAAAAUSgAGCxAAAABtyJALDb25lAAATbnRvHQCnb3VuaW5pdB4AaXQKAA0AEoABguAAAAAbbCQBxHJyb3cAACJ1dBoAHABtDgARAPIAiAAAAQQAAAAQw3BoYQAADAASAAIAngAAAAJsbgABDAAAABDBKQAAAAAAgAwLRksx9MBLqOL00Q8a155DJOJdHdYHe/MuvXwrMo20dlxkw7cBivsY7gNoQNRHe83CdK8Q15aKcRoi2zkL4AAAAADgAAAAAQxmBJ6NT+KRRjbgAAAAC1MtovEZIbMJoIlABw9VW3Ug8Z9mJ38mxpYUcYtI1ihhYmNmaW1wcnN0dSk4ZGVnaGxub3d5","N":"B`","E":"OjAAAAAAAAA=","H":"fhLvJzk="},"crateNames":{"N":"a","E":"OjAAAAAAAAA=","H":"mN5DrcE="},"name":{"N":"B`","E":"OjAAAAAAAAA=","H":"6ifKwgI="},"path":{"N":"Ad","E":"OjAAAAEAAAAAAAsAEAAAAAQABwAIAA0AEQAWABcAGAAZABwAHQAfAA==","H":"TWJD83E="},"entry":{"N":"n","E":"OzAAAAEAABEABwAAAAMABQABAAkAAQAOAAIAEgADABoAAQAeAAAA","H":"5EKobdw="},"desc":{"N":"e","E":"OzAAAAEAABoABAAAAAMABQABAAkAAQANABIA","H":"ibnJWOQ="},"function":{"N":"k","E":"OzAAAAEAABQABgAAAAYACQADAA4AAgASAAMAGgABAB4AAAA=","H":"RB5xVFw="},"type":{"N":"i","E":"OzAAAAEAABYABwACAAAABAAEAAoAAAAMAAIAEQADABYABAAcAAMA","H":"SYJSHHw="},"alias":{"N":"`","E":"OzAAAAEAAB8AAQAAAB8A","H":"YhUMUyw="},"generic_inverted_index":{"N":"b","E":"OjAAAAAAAAA=","H":"+x59Q+s="}}')
\ No newline at end of file
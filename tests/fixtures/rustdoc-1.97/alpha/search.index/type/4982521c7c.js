@@ -0,0 +1 @@
+rd_("Ak[\"\",\"AAAAAAACHQAAAB8AAAA=\"]Ag[\"AAAAAAABHwAAAA==\",\"\"]Bo[\"AAAAAAADHAAAAB0AAAAfAAAA\",\"AAAAAAABHAAAAA==\"]2Ao[\"AAAAAQ0AAAA=\",\"AAAAAQ0AAAA=\"]Ai[\"\",\"AAABGQAAAAEYAAAA\",1]Ac[\"\",\"AAABFgAAAA==\"]Ai[\"AAAAAAABHwAAAA==\",\"\",1]Dm[\"AAACFgAAABcAAAACDQAAABEAAAAAAxwAAAAdAAAAHwAAAA==\",\"AAAAAREAAAAAARwAAAA=\",1]")
\ No newline at end of file
@@ -0,0 +1 @@
+rn_("BYnAAAAQBgCmHQAlgsAAABMaAAsAFAAFAsAAACAPABUAEhwAZG4hwAAAEQAWAFcFAP0oIQfFAAAAGsEAAAAawgAAAA7TCgAAAAIO")
\ No newline at end of file
@@ -0,0 +1 @@
+rn_("BYrAAAARFABQBQAIABMAGAAcAAUCwAAAoh0AMAkAHQAeAG50JQPAAAAgEQAWABMcAKcdAGRpdAoADADr/RQBRMAAAAAa4AAAAAI=")
\ No newline at end of file
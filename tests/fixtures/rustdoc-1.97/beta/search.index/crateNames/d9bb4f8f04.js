@@ -0,0 +1 @@
+rd_("dbeta")
\ No newline at end of file
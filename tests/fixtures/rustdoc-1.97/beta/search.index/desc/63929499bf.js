@@ -0,0 +1 @@
+rd_("oThe beta crate.AoReturns the argument unchanged.BaCalls <code>U::from(self)</code>.nAnother thing.")
\ No newline at end of file
@@ -0,0 +1 @@
+rd_("A`[\"{cc{}}\",[\"T\"]]Aa[\"{{}c{}}\",[\"U\"]]Ae[\"{{{Cf{Ah}}}Ah}\",[]]Ai[\"{Cf{{Cf{c}}}{}}\",[\"T\"]]m[\"{CfB`}\",[]]Aa[\"{Cfc{}}\",[\"T\"]]An[\"{c{{An{e}}}{}{}}\",[\"U\",\"T\"]]Ai[\"{{}{{An{c}}}{}}\",[\"U\"]]Ba[\"{{{Cf{h}}}{{Cf{hc}}}{}}\",[\"T\"]]Al[\"{{Cf{Cf{hc}}}Ad{}}\",[\"T\"]]Ag[\"{{Cf{Bj{hd}}}Ad}\",[]]")
\ No newline at end of file
@@ -0,0 +1 @@
+rd_("b()bu8canycmutdfromdintodbeta21dunitecloneeother1fborrowfresultftypeid2gtoownedgtryfromgtryintogpointer4321iborrowmutireference1icloneintomclonetouninit0")
\ No newline at end of file
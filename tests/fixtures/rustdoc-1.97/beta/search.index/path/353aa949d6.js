@@ -0,0 +1 @@
+rd_("f[1,\"\"]0A`[10,\"core::any\"]f[0,\"\"]Ad[10,\"core::convert\"]0f[3,\"\"]4Ab[10,\"core::clone\"]Aa[5,\"beta\",\"beta\"]Ac[10,\"core::borrow\"]Ba[6,\"core::result\",\"core::result\"]Ak[5,\"core::any\",\"core::any\"]Ad[10,\"alloc::borrow\"]77:3:5")
\ No newline at end of file
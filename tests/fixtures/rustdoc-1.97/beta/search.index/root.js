@@ -0,0 +1 @@
+rr_('{"normalizedName":{"I":"FQPAAAAjEgAXACMTABgAIw8AFQBmaXACACUCwAAAIhkAGwAiEQAWAG1uDQAQACUCwgAAExwApx0AaXQKAAwAGwLgAAAADm90QcAAAA8AEQAVABYAMcAAAAMAGQAbAKHCAAAdACKBaQEBoAAAAAl0bqKAAgKgAAAAAcAAAAAOOG5sdCDEcGVpZAAADwAVAAQCwXkAACNyb20SABcAI250bxMAGABmaaHFAAAdACDEd25lZAAAEQAWAPIAiJAA1QUAAAADCwUEcgADA+AAAAAGwAAAABTBAAAAC29yeWFlaADXAwAAAAYFCwICAQUCwAAAIxIAFwAjEwAYAGZpJQHCAAAiGQAbAG0NABAAJQHAAAAiGQAbAG0NABAAQcAAAAQABwASABcA+wJtdxDDdWx0AAAOABDGZXJlbmNlAAAaADKAAgHBAAAAGmZzbvIAggNvcnkA0gsAAAAJIcIAAA8AFQAQxWludGVyAAAUAPKAiIIlAcMAACIZABsAbQ0AEAAhxAAAEQAWACUCwQAAExwApx0AaXQKAAwAEMNoZXIAAAsA1A8AAf0AcRkAwwAAABQMAQIDEx10ANUFAAAAAwsFBABBdAEDAAAA0hkAAAAC8gCQAADUBAAAAAMLBTHAAAAJAB0AHgAhwAAADwAVAKHBAAAdAABA0gUAAAAD0xMAAAAFBBKAiILAAAAAFPKAAYR08oABAm5kdABCb23SBAAAAAPSEgAAAAUSgAGCxAAAABpyJALDb25lAAATbnRvHACnb3VuaW5pdB0AaXQKAAwAEoABguAAAAAabCQBxHJyb3cAACJ1dBkAGwBtDQAQABDCdGEAAAYA8oCKAAAAAQCeAAAAAm4AAQYAAAAQwSkAAAAAAIAMCkZLMfTAJoC3hWVBs53/QkZ6I/DaWUGqvBFHBVbBK1gFW2SmIouaxTljqsAumHM0NjPiSSyhOGMtCNhl7eAAAAAA4AAAAAFhdiKL4F4Oe8bqwQAAAAsBtyJhMXMKpL1VwgAAAA40QYHFKEkN+31XKGFiY2ZpbW9wcnR1KThkZWhsbnN3eQ==","N":"Ao","E":"OjAAAAAAAAA=","H":"dnepzvk="},"crateNames":{"N":"a","E":"OjAAAAAAAAA=","H":"2btPjwQ="},"name":{"N":"Ao","E":"OjAAAAAAAAA=","H":"KLeFeU8="},"path":{"N":"Ad","E":"OjAAAAEAAAAAAAoAEAAAAAcACAAMABAAFQAWABcAGAAbABwAHgA=","H":"NTqpSdY="},"entry":{"N":"m","E":"OzAAAAEAABEABgAAAAUACQABAA0AAgARAAMAGQABAB0AAAA=","H":"EGSYweE="},"desc":{"N":"d","E":"OzAAAAEAABoAAwAAAAUACQABAAwAEgA=","H":"Y5KUmb8="},"function":{"N":"k","E":"OzAAAAEAABMABgAAAAYACQACAA0AAgARAAMAGQABAB0AAAA=","H":"ZoWW7vM="},"type":{"N":"i","E":"OzAAAAEAABUABwACAAAABAAEAAoAAAAMAAEAEAADABUABAAbAAMA","H":"x8A8pZE="},"alias":{"N":"`","E":"OzAAAAEAAB4AAQAAAB4A","H":"YhUMUyw="},"generic_inverted_index":{"N":"b","E":"OjAAAAAAAAA=","H":"GuOho28="}}')
\ No newline at end of file
@@ -0,0 +1 @@
+stringdex-2da4960a.js
@@ -0,0 +1 @@
+rn_("BYnAAAAQBwC2KgAlgsAAABMlAA4AGwAFAsAAADAVABwAHQCiKABkbjHAAAAYAB4AHwBXBQD9KCEHxQAAACXBAAAAJcIAAAAU4g0AAAARAAAAJQAAAAEA")
\ No newline at end of file
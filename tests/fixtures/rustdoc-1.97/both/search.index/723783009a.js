@@ -0,0 +1 @@
+rn_("AUDkBgAAAAoAAAAaAAAAIgAAACgAAAABARArisAAAAAbBQLAAACyKgBADAAqACsALABudDUDwAAAMBgAHgAfAKMoALcqAGRpdA0AEQASAK8B/VQBRMAAAAAl4AAAAA/gAAAAAg==")
\ No newline at end of file
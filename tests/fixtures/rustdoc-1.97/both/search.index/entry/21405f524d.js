@@ -0,0 +1 @@
+rd_("Ac[16,2,17,0,0,0,0,0]Aa[7,3,0,0,0,0,0,0]Af[16,13,17,17,16,6,0,0]Ac[7,13,8,8,15,6,0,0]Af[16,13,17,17,16,7,0,0]Ac[7,13,8,8,15,7,0,0]Aa[7,5,8,0,0,0,0,0]Ac[16,5,17,0,0,0,0,0]Ab[16,3,0,0,0,0,0,0]Ag[16,13,17,17,16,14,0,0]Ad[7,13,8,8,15,14,0,0]Ag[16,13,17,17,16,20,0,0]Ad[7,13,8,8,15,20,0,0]Af[16,13,17,17,16,3,0,0]Ac[7,13,8,8,15,3,0,0]Ag[16,13,17,17,16,25,0,0]Ad[7,13,8,8,15,25,0,0]Ag[16,13,17,17,16,26,0,0]Ad[7,13,8,8,15,26,0,0]Ag[16,13,17,17,16,27,0,0]Ad[7,13,8,8,15,27,0,0]Ag[16,13,17,17,16,37,0,0]Ad[7,13,8,8,15,37,0,0]76Ag[16,13,17,17,16,43,0,1]Ad[7,13,8,8,15,43,0,1]")
\ No newline at end of file
@@ -0,0 +1 @@
+rd_("A`[\"{cc{}}\",[\"T\"]]0Aa[\"{{}c{}}\",[\"U\"]]0Ae[\"{{{Dl{B`}}}B`}\",[]]Ae[\"{{{Dl{An}}}An}\",[]]Ai[\"{Dl{{Dl{c}}}{}}\",[\"T\"]]0m[\"{DlBl}\",[]]0Aa[\"{Dlc{}}\",[\"T\"]]0An[\"{c{{Bj{e}}}{}{}}\",[\"U\",\"T\"]]0Ai[\"{{}{{Bj{c}}}{}}\",[\"U\"]]0Ba[\"{{{Dl{h}}}{{Dl{hc}}}{}}\",[\"T\"]]0Al[\"{{Dl{Dl{hc}}}Aj{}}\",[\"T\"]]0Ag[\"{{Dl{Ch{hd}}}Aj}\",[]]0")
\ No newline at end of file
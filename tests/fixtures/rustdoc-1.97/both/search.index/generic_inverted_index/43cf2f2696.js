@@ -0,0 +1 @@
+rb_("RGYAAgoAAAALAAAABggAAAAJAAAAHgAAAB8AAAAiAAAAIwAAAAQWAAAAFwAAACAAAAAhAAAAAAQmAAAAJwAAACgAAAApAAAAbAAAAAIgAAAAIQAAAA==")
\ No newline at end of file
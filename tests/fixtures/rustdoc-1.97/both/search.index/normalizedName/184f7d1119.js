@@ -0,0 +1 @@
+rd_("b()bu8canycmutcsubdfromdintodbeta2211dunitecloneeotherethingealpha33fborrowfresultftypeid22gtoownedgtryfromgtryintogpointer44332211iborrowmutireference11icloneinto0mclonetouninit00")
\ No newline at end of file
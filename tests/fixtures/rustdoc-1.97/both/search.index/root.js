@@ -0,0 +1 @@
+rr_('{"normalizedName":{"I":"FQPAAAAzGQAgACEAMxoAIgAjADMVABwAHQBmaXACADUCwAAAMiQAJgAnADIYAB4AHwBtbhMAFgAXADUCwgAAoygAtyoAaXQNABEAEgAbA8EAAAAQ4AAAABRvcHQFh8AAABAQABEOABIPAGHAAAAVABgAHAAdAB4AHwBBwAAAAwAkACYAJwCxwgAAKgAigWkBAaAAAAAMdG4mAYACwAGBoAAAAAHgAAAABMAAAAAUOG4wxHBlaWQAABUAHAAdAAQCwXkAADNyb20ZACAAIQAzbnRvGgAiACMAZmmxxQAAKgAwxHduZWQAABgAHgAfAPIAiJAA5AYAAAAKAAAAGgAAACIAAAAoAAAAAQEQEMJuZwAADwASgISCwAAAAA72AAD9gEBBg+AAAAAHwAAAABsA5QMAAAAMAAAAFAAAACQAAAAmAAAAKgAAAAAAEgCBdQEBoAAAAATAAAAAFGJsBQLAAAAzGQAgACEAMxoAIgAjAGZpNQHCAAAyJAAmACcAbRMAFgAXADUBwAAAMiQAJgAnAG0TABYAFwBhwAAABQAIAAkAGQAgACEA+wJtdxDDdWx0AAAUABDGZXJlbmNlAAAlADKAAgHBAAAAJWZzbvIAggNvcnkA0g4AAAANMcIAABUAHAAdABDFaW50ZXIAABsAMoCIAsAAAAAQZWg1AcMAADIkACYAJwBtEwAWABcAMcQAABgAHgAfADUCwQAAoygAtyoAaXQNABEAEgAQw2hlcgAADgDUDwAB/QBxGQDDAAAAGwwBAgMWIXQA5AYAAAAKAAAAGgAAACIAAAAoAAAAAQEQAEF0AQMAAADhJAAAACYAAAAB8gCQAADjBQAAAAgAAAAZAAAAIAAAAAEBQcAAAAwAKgArACwAMcAAABUAHAAdALHBAAAqAABA4QYAAAAKAAAAAeIaAAAAIgAAACgAAAABEBKAiILAAAAAG1KAAQLgAAAAD3RnafKAAQJuZHQAQm9t4QUAAAAIAAAAAeEZAAAAIAAAAAESgAGCxAAAACVyNALDb25lAACjbnRvKAC3b3VuaW5pdCoAaXQNABEAEgASgAGC4AAAACVsNAHEcnJvdwAAMnV0JAAmACcAbRMAFgAXABDCdGEAAAcA8gCKAAABBAAAABDDcGhhAAAQABIAAgCeAAAAAmxuANIHAAAACRDBKQAAAAAAgA0KRksx9MAwywQjpk3rxgyWSUzT5nwKWrcnknKPIrXZU+7N2ngIpe08817gKIAVBDvyegoMtwdy+z8FYo6KGDVUT97gAAAAAOAAAAABNW94+MIFmPAQe+AAAAAPAb7ESfJcBUXM1XI3gwCaTMoTAhRczpJwtShhYmNmaW1vcHJzdHUpOGRlZ2hsbnd5","N":"Bm","E":"OjAAAAAAAAA=","H":"GE99ERk="},"crateNames":{"N":"b","E":"OjAAAAAAAAA=","H":"/8fxCAw="},"name":{"N":"Bm","E":"OjAAAAAAAAA=","H":"BqtClrg="},"path":{"N":"Af","E":"OzAAAAEAABYABwAEAAAACAADABEAAQAWAAEAHAAHACYAAwArAAEA","H":"R5/i0Ig="},"entry":{"N":"Ak","E":"OzAAAAEAABEABwAAAAMABQABAAwAAQATAAIAGAADACQAAQAqAAAA","H":"IUBfUk0="},"desc":{"N":"i","E":"OzAAAAEAACMABAAAAAMABQABAAwAAQARABsA","H":"Ut0k4Yw="},"function":{"N":"Af","E":"OzAAAAEAABYABgAAAAcADAAEABMAAgAYAAMAJAABACoAAAA=","H":"zRDLJPw="},"type":{"N":"j","E":"OzAAAAEAACIABwACAAAABAAHAA0AAAAQAAMAFgAEABwACAAmAAYA","H":"STvPVAo="},"alias":{"N":"`","E":"OzAAAAEAACwAAQAAACwA","H":"YhUMUyw="},"generic_inverted_index":{"N":"b","E":"OjAAAAAAAAA=","H":"Q88vJpY="}}')
\ No newline at end of file
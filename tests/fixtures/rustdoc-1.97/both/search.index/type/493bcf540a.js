@@ -0,0 +1 @@
+rd_("Bg[\"\",\"AAAAAAAEKAAAACkAAAArAAAALAAAAA==\"]Ak[\"AAAAAAACKwAAACwAAAA=\",\"\"]Ck[\"AAAAAAA7MAAAAQAABQACACYAAwArAAEA\",\"AAAAAAACJgAAACcAAAA=\"]2Ao[\"AAAAARIAAAA=\",\"AAAAARIAAAA=\"]Ao[\"AAAAAREAAAA=\",\"AAAAAREAAAA=\"]Be[\"\",\"AAACIgAAACMAAAACIAAAACEAAAA=\",1]Ag[\"\",\"AAACHAAAAB0AAAA=\"]Am[\"AAAAAAACKwAAACwAAAA=\",\"\",1]Ge[\"AAAEHAAAAB0AAAAeAAAAHwAAAAQRAAAAEgAAABYAAAAXAAAAADswAAABAAAFAAIAJgADACsAAQA=\",\"AAAAAhYAAAAXAAAAAAImAAAAJwAAAA==\",1]")
\ No newline at end of file
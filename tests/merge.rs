@@ -0,0 +1,651 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+use doc_merge::{
+    ConflictPolicy, Glob, Latest, LinkMode, MergeError, Merger, Prune, Unchanged, VersionMismatch,
+};
+
+/// The output of rustdoc 1.95 for one of the fixtures: `alpha`, `beta`, or `both`.
+fn fixture(name: &str) -> PathBuf {
+    toolchain_fixture("1.95", name)
+}
+
+/// The output of a `version` of rustdoc for one of the fixtures.
+fn toolchain_fixture(version: &str, name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(format!("rustdoc-{version}"))
+        .join(name)
+}
+
+/// Write the documentation of one crate in `dir` as rustdoc 1.90 would, with the `search-index.js`
+/// of the fixture of the same name.
+fn write_rustdoc_1_90_docs(dir: &Path, crate_name: &str) {
+    let data = match crate_name {
+        "alpha" => {
+            r#"{"t":"FNNNNNNNCNNNN","n":["Thing","borrow","borrow_mut","clone","clone_into","clone_to_uninit","from","into","sub","to_owned","try_from","try_into","type_id"],"q":[[0,"alpha"],[13,"core::result"],[14,"core::any"]],"i":"`f000000`0000","e":"OzAAAAEAAAgAAgACAAQACgADAA=="}"#
+        }
+        _ => {
+            r#"{"t":"FNNNNNNNNNNN","n":["Other","borrow","borrow_mut","clone","clone_into","clone_to_uninit","from","into","to_owned","try_from","try_into","type_id"],"q":[[0,"beta"],[12,"core::result"],[13,"core::any"]],"i":"`f0000000000","e":"OzAAAAEAAAgAAgACAAQACQADAA=="}"#
+        }
+    };
+    fs::create_dir_all(dir.join(crate_name)).unwrap();
+    fs::write(dir.join(crate_name).join("index.html"), crate_name).unwrap();
+    fs::write(
+        dir.join("search-index.js"),
+        format!("var searchIndex = new Map(JSON.parse('[[\"{crate_name}\",{data}]]'));\n"),
+    )
+    .unwrap();
+}
+
+/// Two sources below `root`, `first` and `second`, that both document `alpha` as rustdoc 1.90
+/// would. The crate's page names its source, and so does the name of its struct, `Thing_first` or
+/// `Thing_second`. The second source was built long before the first.
+fn conflicting_sources(root: &Path) -> [PathBuf; 2] {
+    ["first", "second"].map(|name| {
+        let src = root.join(name);
+        write_rustdoc_1_90_docs(&src, "alpha");
+        fs::write(src.join("alpha/index.html"), name).unwrap();
+        let index = src.join("search-index.js");
+        let js = fs::read_to_string(&index).unwrap();
+        fs::write(
+            &index,
+            js.replace(r#""Thing""#, &format!(r#""Thing_{name}""#)),
+        )
+        .unwrap();
+        src
+    })
+}
+
+/// Every file below `root`, relative to it.
+fn files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut dirs = vec![root.to_owned()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                files.push(path.strip_prefix(root).unwrap().to_owned());
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Copy every file below `from` to the same place below `to`.
+fn copy_tree(from: &Path, to: &Path) {
+    for rel in files(from) {
+        fs::create_dir_all(to.join(&rel).parent().unwrap()).unwrap();
+        fs::copy(from.join(&rel), to.join(&rel)).unwrap();
+    }
+}
+
+/// Every file below `root` with its contents and modification time.
+fn snapshot(root: &Path) -> Vec<(PathBuf, Vec<u8>, SystemTime)> {
+    files(root)
+        .into_iter()
+        .map(|rel| {
+            let path = root.join(&rel);
+            let modified = fs::metadata(&path).unwrap().modified().unwrap();
+            (rel, fs::read(path).unwrap(), modified)
+        })
+        .collect()
+}
+
+/// Check that the files shared between crates in `dest` are those rustdoc writes when it
+/// documents both crates together.
+fn assert_shared_files_like_rustdoc(dest: &Path) {
+    assert_same_files(dest, &fixture("both"));
+}
+
+/// Check that every file below `expected` is in `dest`, with the same contents.
+fn assert_same_files(dest: &Path, expected: &Path) {
+    for rel in files(expected) {
+        assert_eq!(
+            fs::read(dest.join(&rel)).unwrap(),
+            fs::read(expected.join(&rel)).unwrap(),
+            "{}",
+            rel.display()
+        );
+    }
+}
+
+#[test]
+fn merges_crates_like_rustdoc() {
+    let site = tempfile::tempdir().unwrap();
+    let dest = site.path().join("docs");
+    let report = Merger::new(&dest)
+        .source(fixture("alpha"))
+        .source(fixture("beta"))
+        .execute()
+        .unwrap();
+
+    assert_eq!(
+        report.crates,
+        BTreeMap::from([
+            ("alpha".to_owned(), fixture("alpha")),
+            ("beta".to_owned(), fixture("beta")),
+        ])
+    );
+    assert!(report.updated.is_empty());
+    assert!(report.conflicts.is_empty());
+    assert!(report.excluded.is_empty());
+    assert_eq!(report.rustdoc_versions.len(), 2);
+    assert_eq!(report.index, Some(dest.join("index.html")));
+    assert_eq!(report.previous, None);
+    assert_shared_files_like_rustdoc(&dest);
+    assert!(dest.join("alpha/struct.Thing.html").is_file());
+    assert!(dest.join("beta/struct.Other.html").is_file());
+    let landing = fs::read_to_string(dest.join("index.html")).unwrap();
+    assert!(landing.contains("alpha/index.html") && landing.contains("beta/index.html"));
+}
+
+#[test]
+fn adds_crates_to_a_site() {
+    let site = tempfile::tempdir().unwrap();
+    let dest = site.path().join("docs");
+    let report = Merger::new(&dest)
+        .source(fixture("alpha"))
+        .incremental(true)
+        .execute()
+        .unwrap();
+    assert_eq!(report.crates.keys().collect::<Vec<_>>(), ["alpha"]);
+
+    let report = Merger::new(&dest)
+        .source(fixture("beta"))
+        .incremental(true)
+        .execute()
+        .unwrap();
+    assert_eq!(
+        report.crates,
+        BTreeMap::from([
+            ("alpha".to_owned(), dest.clone()),
+            ("beta".to_owned(), fixture("beta")),
+        ])
+    );
+    assert!(report.updated.is_empty());
+    assert_eq!(
+        report.previous,
+        Some(site.path().join(".docs.previous")),
+        "the site before the merge is kept"
+    );
+    assert!(dest.join("alpha/struct.Thing.html").is_file());
+    assert!(dest.join("beta/struct.Other.html").is_file());
+    assert_shared_files_like_rustdoc(&dest);
+
+    let report = Merger::new(&dest)
+        .source(fixture("alpha"))
+        .incremental(true)
+        .execute()
+        .unwrap();
+    assert_eq!(report.updated.iter().collect::<Vec<_>>(), ["alpha"]);
+    assert_eq!(report.crates.keys().collect::<Vec<_>>(), ["alpha", "beta"]);
+    assert_shared_files_like_rustdoc(&dest);
+}
+
+#[test]
+fn removes_crates_from_a_site() {
+    let site = tempfile::tempdir().unwrap();
+    let dest = site.path().join("docs");
+    Merger::new(&dest)
+        .source(fixture("alpha"))
+        .source(fixture("beta"))
+        .execute()
+        .unwrap();
+
+    doc_merge::remove(&dest, ["beta"]).unwrap();
+    assert!(!dest.join("beta").exists());
+    assert!(!dest.join("src/beta").exists());
+    for rel in ["crates.js", "src-files.js"] {
+        assert_eq!(
+            fs::read(dest.join(rel)).unwrap(),
+            fs::read(fixture("alpha").join(rel)).unwrap(),
+            "{rel}"
+        );
+    }
+    assert_eq!(
+        fs::read(dest.join("search.index/root.js")).unwrap(),
+        fs::read(fixture("alpha").join("search.index/root.js")).unwrap()
+    );
+    let report = Merger::new(&dest)
+        .source(fixture("beta"))
+        .incremental(true)
+        .execute()
+        .unwrap();
+    assert_eq!(report.crates.len(), 2);
+
+    assert!(doc_merge::remove(&dest, ["gamma"]).is_err());
+}
+
+#[test]
+fn prunes_stale_files() {
+    let site = tempfile::tempdir().unwrap();
+    let dest = site.path().join("docs");
+    let merger = Merger::new(&dest)
+        .source(fixture("alpha"))
+        .source(fixture("beta"));
+    merger.execute().unwrap();
+    fs::write(dest.join("alpha/struct.Gone.html"), "").unwrap();
+
+    let report = merger.clone().prune(Prune::DryRun).execute().unwrap();
+    assert_eq!(report.stale, [PathBuf::from("alpha/struct.Gone.html")]);
+    assert!(dest.join("alpha/struct.Gone.html").is_file());
+
+    let report = merger.clone().prune(Prune::Delete).execute().unwrap();
+    assert_eq!(report.stale, [PathBuf::from("alpha/struct.Gone.html")]);
+    assert!(!dest.join("alpha/struct.Gone.html").exists());
+
+    let report = merger.prune(Prune::Delete).execute().unwrap();
+    assert!(report.stale.is_empty());
+}
+
+#[test]
+fn merges_versions_side_by_side() {
+    let site = tempfile::tempdir().unwrap();
+    let merger = Merger::new(site.path())
+        .source(fixture("alpha"))
+        .source(fixture("beta"))
+        .latest(Latest::Redirect);
+
+    let report = merger.clone().version("1.0").execute().unwrap();
+    assert_eq!(report.versions, ["1.0"]);
+    assert_eq!(report.latest.as_deref(), Some("1.0"));
+
+    let report = merger.version("1.1").execute().unwrap();
+    assert_eq!(report.versions, ["1.1", "1.0"]);
+    assert_eq!(report.latest.as_deref(), Some("1.1"));
+    assert_eq!(report.crates.keys().collect::<Vec<_>>(), ["alpha", "beta"]);
+    assert_shared_files_like_rustdoc(&site.path().join("1.1"));
+    for version in ["1.0", "1.1"] {
+        let page = fs::read_to_string(site.path().join(version).join("alpha/index.html")).unwrap();
+        assert!(
+            page.contains(&format!("data-doc-merge-version=\"{version}\"")),
+            "{version}"
+        );
+    }
+    assert!(site.path().join("latest/alpha/index.html").is_file());
+    assert_eq!(
+        fs::read_to_string(site.path().join("versions.json")).unwrap(),
+        "[\n  \"1.1\",\n  \"1.0\"\n]\n"
+    );
+}
+
+#[test]
+fn links_files_that_outlive_other_versions() {
+    for mode in [LinkMode::Hardlink, LinkMode::Reflink, LinkMode::Symlink] {
+        let site = tempfile::tempdir().unwrap();
+        let merger = Merger::new(site.path())
+            .source(fixture("alpha"))
+            .source(fixture("beta"))
+            .link_mode(mode);
+        merger.clone().version("1").execute().unwrap();
+        let report = merger.clone().version("2").execute().unwrap();
+        if mode == LinkMode::Symlink {
+            assert_eq!(report.files_linked, 0, "{mode:?}");
+        } else {
+            assert!(report.files_linked > 0, "{mode:?}");
+        }
+
+        // merge the first version again, and then drop a crate from it
+        merger.version("1").prune(Prune::Delete).execute().unwrap();
+        doc_merge::remove(&site.path().join("1"), ["beta"]).unwrap();
+
+        let dest = site.path().join("2");
+        for rel in files(&dest) {
+            assert!(
+                fs::read(dest.join(&rel)).is_ok(),
+                "{mode:?}: {}",
+                rel.display()
+            );
+        }
+        assert_shared_files_like_rustdoc(&dest);
+        assert!(dest.join("beta/struct.Other.html").is_file());
+    }
+}
+
+#[test]
+fn replaces_the_search_index_of_another_generation() {
+    let site = tempfile::tempdir().unwrap();
+    let dest = site.path().join("docs");
+    let old = site.path().join("old");
+    for name in ["alpha", "beta"] {
+        write_rustdoc_1_90_docs(&old.join(name), name);
+    }
+    let new = Merger::new(&dest)
+        .source(fixture("alpha"))
+        .source(fixture("beta"));
+    new.execute().unwrap();
+
+    Merger::new(&dest)
+        .source(old.join("alpha"))
+        .source(old.join("beta"))
+        .execute()
+        .unwrap();
+    assert!(!dest.join("search.index").exists());
+    assert!(dest.join("search-index.js").is_file());
+    doc_merge::remove(&dest, ["beta"]).unwrap();
+    let report = Merger::new(&dest)
+        .source(old.join("beta"))
+        .incremental(true)
+        .execute()
+        .unwrap();
+    assert_eq!(report.crates.keys().collect::<Vec<_>>(), ["alpha", "beta"]);
+
+    new.execute().unwrap();
+    assert!(!dest.join("search-index.js").exists());
+    assert_shared_files_like_rustdoc(&dest);
+    doc_merge::remove(&dest, ["beta"]).unwrap();
+}
+
+#[test]
+fn merges_the_search_index_of_stringdex_0_0_6() {
+    let site = tempfile::tempdir().unwrap();
+    let dest = site.path().join("docs");
+    // only the search index is kept of the 1.97 fixtures, so give each crate a page of its own
+    let sources = ["alpha", "beta"].map(|name| {
+        let src = site.path().join(name);
+        copy_tree(&toolchain_fixture("1.97", name), &src);
+        fs::create_dir_all(src.join(name)).unwrap();
+        fs::write(src.join(name).join("index.html"), name).unwrap();
+        src
+    });
+    let report = Merger::new(&dest).sources(&sources).execute().unwrap();
+    assert_eq!(report.crates.keys().collect::<Vec<_>>(), ["alpha", "beta"]);
+    assert_same_files(&dest, &toolchain_fixture("1.97", "both"));
+}
+
+#[test]
+fn resolves_conflicts_by_the_policy() {
+    for (policy, winner) in [
+        (ConflictPolicy::FirstWins, "first"),
+        (ConflictPolicy::LastWins, "second"),
+        (ConflictPolicy::PreferNewest, "first"),
+    ] {
+        let site = tempfile::tempdir().unwrap();
+        let dest = site.path().join("docs");
+        let sources = conflicting_sources(site.path());
+        fs::File::options()
+            .write(true)
+            .open(sources[1].join("alpha/index.html"))
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH)
+            .unwrap();
+
+        let report = Merger::new(&dest)
+            .sources(&sources)
+            .on_conflict(policy)
+            .execute()
+            .unwrap();
+        let chosen = site.path().join(winner);
+        assert_eq!(report.crates["alpha"], chosen, "{policy:?}");
+        assert_eq!(report.conflicts.len(), 1, "{policy:?}");
+        assert_eq!(report.conflicts[0].sources, sources, "{policy:?}");
+        assert_eq!(report.conflicts[0].chosen, chosen, "{policy:?}");
+        assert_eq!(
+            fs::read_to_string(dest.join("alpha/index.html")).unwrap(),
+            winner,
+            "{policy:?}"
+        );
+        let index = fs::read_to_string(dest.join("search-index.js")).unwrap();
+        assert!(index.contains(&format!("Thing_{winner}")), "{policy:?}");
+        assert_eq!(index.matches("Thing_").count(), 1, "{policy:?}");
+    }
+
+    let site = tempfile::tempdir().unwrap();
+    let dest = site.path().join("docs");
+    let sources = conflicting_sources(site.path());
+    assert!(Merger::new(&dest)
+        .sources(&sources)
+        .on_conflict(ConflictPolicy::Error)
+        .execute()
+        .is_err());
+    assert!(!dest.exists());
+}
+
+#[test]
+fn dry_run_leaves_the_site_alone() {
+    let site = tempfile::tempdir().unwrap();
+    let dest = site.path().join("docs");
+    Merger::new(&dest)
+        .source(fixture("alpha"))
+        .incremental(true)
+        .execute()
+        .unwrap();
+    let before = snapshot(site.path());
+    let add = Merger::new(&dest)
+        .source(fixture("alpha"))
+        .source(fixture("beta"))
+        .incremental(true)
+        .prune(Prune::Delete);
+
+    let planned = add.clone().dry_run(true).execute().unwrap();
+    assert!(snapshot(site.path()) == before);
+    assert!(!site.path().join(".docs.staging").exists());
+    let dry_run = || {
+        let output = Command::new(env!("CARGO_BIN_EXE_doc-merge"))
+            .args(["add", "--dry-run", "--prune", "--dest"])
+            .arg(&dest)
+            .arg("--src")
+            .arg(fixture("alpha"))
+            .arg("--src")
+            .arg(fixture("beta"))
+            .current_dir(site.path())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let printed = dry_run();
+    assert!(printed.contains("beta (from"), "{printed}");
+    assert_eq!(dry_run(), printed);
+    assert!(snapshot(site.path()) == before);
+
+    let report = add.execute().unwrap();
+    assert_eq!(planned.crates, report.crates);
+    assert_eq!(planned.updated, report.updated);
+    assert_eq!(planned.copies, report.copies);
+    assert_eq!(planned.stale, report.stale);
+    assert_eq!(planned.index, report.index);
+}
+
+#[test]
+fn skips_unchanged_files() {
+    for unchanged in [Unchanged::Mtime, Unchanged::Content] {
+        let site = tempfile::tempdir().unwrap();
+        let dest = site.path().join("docs");
+        let merger = Merger::new(&dest)
+            .source(fixture("alpha"))
+            .source(fixture("beta"))
+            .skip_unchanged(unchanged);
+        let report = merger.execute().unwrap();
+        assert_eq!(report.files_skipped, 0, "{unchanged:?}");
+        let before = snapshot(&dest);
+        #[cfg(unix)]
+        let inodes = || {
+            use std::os::unix::fs::MetadataExt;
+            files(&dest)
+                .into_iter()
+                .map(|rel| fs::metadata(dest.join(rel)).unwrap().ino())
+                .collect::<Vec<_>>()
+        };
+        #[cfg(unix)]
+        let inodes_before = inodes();
+
+        let report = merger.execute().unwrap();
+        assert_eq!(report.files_written, 0, "{unchanged:?}");
+        assert_eq!(report.files_skipped, before.len(), "{unchanged:?}");
+        assert!(snapshot(&dest) == before, "{unchanged:?}");
+        #[cfg(unix)]
+        assert_eq!(inodes(), inodes_before, "{unchanged:?}");
+    }
+}
+
+#[test]
+fn handles_sources_of_different_rustdoc_versions() {
+    // the 1.95 and 1.97 fixtures lay out their search index differently, so `beta` is made to
+    // look documented by a rustdoc 1.96 with a stylesheet of its own instead
+    let site = tempfile::tempdir().unwrap();
+    let beta = site.path().join("beta");
+    copy_tree(&fixture("beta"), &beta);
+    let (old_css, new_css) = ("rustdoc-b7b9f40b.css", "rustdoc-0a1b2c3d.css");
+    let (old_version, new_version) = (
+        "1.95.0 (59807616e 2026-04-14)",
+        "1.96.0 (0a1b2c3d4 2026-05-26)",
+    );
+    fs::rename(
+        beta.join("static.files").join(old_css),
+        beta.join("static.files").join(new_css),
+    )
+    .unwrap();
+    for rel in files(&beta) {
+        if rel.extension().is_some_and(|ext| ext == "html") {
+            let page = fs::read_to_string(beta.join(&rel)).unwrap();
+            let page = page
+                .replace(old_css, new_css)
+                .replace(old_version, new_version);
+            fs::write(beta.join(&rel), page).unwrap();
+        }
+    }
+    let merger = |policy, dest: &str| {
+        Merger::new(site.path().join(dest))
+            .source(fixture("alpha"))
+            .source(&beta)
+            .on_version_mismatch(policy)
+            .execute()
+    };
+
+    let err = merger(VersionMismatch::Error, "error").unwrap_err();
+    match err.downcast_ref::<MergeError>() {
+        Some(MergeError::VersionMismatch { versions }) => assert_eq!(
+            versions,
+            &BTreeMap::from([
+                (fixture("alpha"), old_version.to_owned()),
+                (beta.clone(), new_version.to_owned()),
+            ])
+        ),
+        _ => panic!("not a version mismatch: {err:?}"),
+    }
+    assert!(!site.path().join("error").exists());
+
+    let report = merger(VersionMismatch::Warn, "warn").unwrap();
+    assert_eq!(report.rustdoc_versions.len(), 2);
+    assert_eq!(
+        fs::read(site.path().join("warn/alpha/index.html")).unwrap(),
+        fs::read(fixture("alpha").join("alpha/index.html")).unwrap()
+    );
+
+    merger(VersionMismatch::Rewrite, "rewrite").unwrap();
+    let dest = site.path().join("rewrite");
+    for page in ["alpha/index.html", "alpha/struct.Thing.html"] {
+        let page = fs::read_to_string(dest.join(page)).unwrap();
+        assert!(page.contains(new_css) && !page.contains(old_css));
+        assert!(page.contains(new_version) && !page.contains(old_version));
+    }
+    assert_eq!(
+        fs::read(dest.join("beta/index.html")).unwrap(),
+        fs::read(beta.join("beta/index.html")).unwrap()
+    );
+    assert!(dest.join("static.files").join(new_css).is_file());
+}
+
+#[test]
+fn excludes_crates_already_in_the_site() {
+    let site = tempfile::tempdir().unwrap();
+    let dest = site.path().join("docs");
+    Merger::new(&dest)
+        .source(fixture("alpha"))
+        .source(fixture("beta"))
+        .execute()
+        .unwrap();
+
+    let report = Merger::new(&dest)
+        .source(fixture("alpha"))
+        .incremental(true)
+        .exclude(Glob::new("be*").unwrap())
+        .execute()
+        .unwrap();
+    assert_eq!(report.crates.keys().collect::<Vec<_>>(), ["alpha"]);
+    assert_eq!(report.excluded.iter().collect::<Vec<_>>(), ["beta"]);
+    assert!(!dest.join("beta").exists());
+    assert!(!dest.join("src/beta").exists());
+    for rel in ["crates.js", "src-files.js", "search.index/root.js"] {
+        assert_eq!(
+            fs::read(dest.join(rel)).unwrap(),
+            fs::read(fixture("alpha").join(rel)).unwrap(),
+            "{rel}"
+        );
+    }
+}
+
+#[test]
+fn copies_in_parallel_like_one_at_a_time() {
+    let site = tempfile::tempdir().unwrap();
+    let merge = |jobs: usize| {
+        let dest = site.path().join(format!("jobs-{jobs}"));
+        let report = Merger::new(&dest)
+            .source(fixture("alpha"))
+            .source(fixture("beta"))
+            .jobs(jobs)
+            .execute()
+            .unwrap();
+        let tree = files(&dest)
+            .into_iter()
+            .map(|rel| {
+                let contents = fs::read(dest.join(&rel)).unwrap();
+                (rel, contents)
+            })
+            .collect::<Vec<_>>();
+        (report, tree)
+    };
+    let (serial, serial_tree) = merge(1);
+    let (parallel, parallel_tree) = merge(8);
+    assert!(parallel_tree == serial_tree);
+    assert_eq!(parallel.crates, serial.crates);
+    assert_eq!(parallel.copies, serial.copies);
+    assert_eq!(parallel.bytes_copied, serial.bytes_copied);
+    assert_eq!(parallel.files_written, serial.files_written);
+}
+
+#[test]
+fn localizes_links_in_implementor_lists() {
+    // beta implements a trait of alpha, which beta's documentation links to at docs.rs
+    let site = tempfile::tempdir().unwrap();
+    let beta = site.path().join("beta");
+    copy_tree(&fixture("beta"), &beta);
+    let impls = Path::new("trait.impl/alpha/trait.Shape.js");
+    fs::create_dir_all(beta.join(impls).parent().unwrap()).unwrap();
+    fs::write(
+        beta.join(impls),
+        r#"(function() {
+    const implementors = Object.fromEntries([["beta",[["impl <a class=\"trait\" href=\"https://docs.rs/alpha/0.1.0/alpha/trait.Shape.html\" title=\"trait alpha::Shape\">Shape</a> for <a class=\"struct\" href=\"beta/struct.Other.html\" title=\"struct beta::Other\">Other</a>",0]]]]);
+    if (window.register_implementors) {
+        window.register_implementors(implementors);
+    } else {
+        window.pending_implementors = implementors;
+    }
+})()
+//{"start":59,"fragment_lengths":[235]}
+"#,
+    )
+    .unwrap();
+
+    let dest = site.path().join("docs");
+    Merger::new(&dest)
+        .source(fixture("alpha"))
+        .source(&beta)
+        .local_links(true)
+        .execute()
+        .unwrap();
+    let merged = fs::read_to_string(dest.join(impls)).unwrap();
+    assert!(
+        merged.contains(r#"href=\"alpha/trait.Shape.html\""#),
+        "{merged}"
+    );
+    assert!(!merged.contains("docs.rs"), "{merged}");
+}